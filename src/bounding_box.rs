@@ -0,0 +1,192 @@
+//! Define a [BoundingBox], the smallest rectangle containing a bunch of coordinates.
+
+use crate::{num, range::Range, Coord, Rect};
+use std::{fmt, ops};
+
+/// The smallest rectangle containing a set of [Coord]s.
+/// Both `min` and `max` are included in the box.
+///
+/// See also [crate::iterator::BoundingBoxExt] to compute it directly from an iterator.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct BoundingBox<I = usize> {
+    pub min: Coord<I>,
+    pub max: Coord<I>,
+}
+
+impl<I: PartialOrd + Copy> BoundingBox<I> {
+    /// Create a bounding box containing a single coordinate.
+    pub fn from_coord(coord: Coord<I>) -> Self {
+        Self {
+            min: coord,
+            max: coord,
+        }
+    }
+
+    /// Compute the bounding box of a bunch of coordinates in one pass.
+    /// Returns `None` if there was no coordinates.
+    /// ```
+    /// use aoc::{BoundingBox, Coord};
+    ///
+    /// let bbox = BoundingBox::from_coords([Coord::at(3, -1), Coord::at(-2, 4), Coord::at(0, 0)]).unwrap();
+    ///
+    /// assert_eq!(bbox.min, Coord::at(-2, -1));
+    /// assert_eq!(bbox.max, Coord::at(3, 4));
+    /// assert!(BoundingBox::<isize>::from_coords([]).is_none());
+    /// ```
+    pub fn from_coords(coords: impl IntoIterator<Item = Coord<I>>) -> Option<Self> {
+        let mut coords = coords.into_iter();
+        let mut bbox = Self::from_coord(coords.next()?);
+        coords.for_each(|coord| bbox.extend(coord));
+        Some(bbox)
+    }
+
+    /// Grow the bounding box so it contains `coord`.
+    /// ```
+    /// use aoc::{BoundingBox, Coord};
+    ///
+    /// let mut bbox = BoundingBox::from_coord(Coord::at(0, 0));
+    /// bbox.extend(Coord::at(2, -3));
+    ///
+    /// assert_eq!(bbox.min, Coord::at(0, -3));
+    /// assert_eq!(bbox.max, Coord::at(2, 0));
+    /// ```
+    pub fn extend(&mut self, coord: Coord<I>) {
+        if coord.x < self.min.x {
+            self.min.x = coord.x;
+        }
+        if coord.y < self.min.y {
+            self.min.y = coord.y;
+        }
+        if coord.x > self.max.x {
+            self.max.x = coord.x;
+        }
+        if coord.y > self.max.y {
+            self.max.y = coord.y;
+        }
+    }
+
+    /// Return `true` if the coordinate is in the bounding box.
+    /// ```
+    /// use aoc::{BoundingBox, Coord};
+    ///
+    /// let bbox = BoundingBox::from_coords([Coord::at(0, 0), Coord::at(2, 2)]).unwrap();
+    ///
+    /// assert!(bbox.contains(&Coord::at(1, 2)));
+    /// assert!(bbox.contains(&Coord::at(0, 0)));
+    /// assert!(!bbox.contains(&Coord::at(3, 1)));
+    /// ```
+    pub fn contains(&self, coord: &Coord<I>) -> bool {
+        (self.min.x..=self.max.x).contains(&coord.x) && (self.min.y..=self.max.y).contains(&coord.y)
+    }
+
+    /// Return the area shared by both bounding box.
+    /// ```
+    /// use aoc::{BoundingBox, Coord};
+    ///
+    /// let left = BoundingBox::from_coords([Coord::at(0, 0), Coord::at(4, 4)]).unwrap();
+    /// let right = BoundingBox::from_coords([Coord::at(2, 3), Coord::at(6, 8)]).unwrap();
+    ///
+    /// assert_eq!(left.intersect(&right), BoundingBox::from_coords([Coord::at(2, 3), Coord::at(4, 4)]));
+    /// assert_eq!(left.intersect(&BoundingBox::from_coord(Coord::at(5, 5))), None);
+    /// ```
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let max_of = |a: I, b: I| if a > b { a } else { b };
+        let min_of = |a: I, b: I| if a < b { a } else { b };
+        let min = Coord::at(
+            max_of(self.min.x, other.min.x),
+            max_of(self.min.y, other.min.y),
+        );
+        let max = Coord::at(
+            min_of(self.max.x, other.max.x),
+            min_of(self.max.y, other.max.y),
+        );
+
+        (min.x <= max.x && min.y <= max.y).then_some(Self { min, max })
+    }
+
+    /// Return the smallest bounding box containing both bounding box.
+    /// ```
+    /// use aoc::{BoundingBox, Coord};
+    ///
+    /// let left = BoundingBox::from_coord(Coord::at(0, 4));
+    /// let right = BoundingBox::from_coord(Coord::at(3, 1));
+    ///
+    /// assert_eq!(left.union(&right), BoundingBox::from_coords([Coord::at(0, 1), Coord::at(3, 4)]).unwrap());
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ret = *self;
+        ret.extend(other.min);
+        ret.extend(other.max);
+        ret
+    }
+}
+
+impl<I> BoundingBox<I>
+where
+    I: ops::Sub<Output = I> + ops::Add<Output = I> + num::One + Copy,
+{
+    /// The number of columns in the bounding box.
+    /// ```
+    /// use aoc::{BoundingBox, Coord};
+    ///
+    /// let bbox = BoundingBox::from_coords([Coord::at(-1, 0), Coord::at(2, 1)]).unwrap();
+    ///
+    /// assert_eq!(bbox.width(), 4);
+    /// assert_eq!(bbox.height(), 2);
+    /// assert_eq!(bbox.area(), 8);
+    /// ```
+    pub fn width(&self) -> I {
+        self.max.x - self.min.x + I::one()
+    }
+
+    /// The number of lines in the bounding box.
+    pub fn height(&self) -> I {
+        self.max.y - self.min.y + I::one()
+    }
+
+    /// The number of coordinates in the bounding box.
+    pub fn area(&self) -> I
+    where
+        I: ops::Mul<Output = I>,
+    {
+        self.width() * self.height()
+    }
+}
+
+impl<I: Ord + Clone + fmt::Debug> BoundingBox<I> {
+    /// Return an iterator over all the coordinates of the bounding box, see [Rect::iter].
+    pub fn iter(&self) -> Range<I> {
+        Rect::from(self.clone()).iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iterator::BoundingBoxExt;
+
+    #[test]
+    fn iterator_extension() {
+        let coords = [Coord::at(3_u8, 1), Coord::at(0, 5), Coord::at(7, 2)];
+
+        let bbox = coords.iter().copied().bounding_box().unwrap();
+        assert_eq!(bbox.min, Coord::at(0, 1));
+        assert_eq!(bbox.max, Coord::at(7, 5));
+        assert_eq!(bbox.area(), 40);
+        assert_eq!(bbox.iter().count(), 40);
+        assert!(coords.iter().all(|coord| bbox.contains(coord)));
+
+        assert!(std::iter::empty::<Coord<f64>>().bounding_box().is_none());
+    }
+
+    #[test]
+    fn union_contains_both() {
+        let left = BoundingBox::from_coords([Coord::at(-3, 2), Coord::at(0, 5)]).unwrap();
+        let right = BoundingBox::from_coords([Coord::at(4, -1), Coord::at(5, 0)]).unwrap();
+        let union = left.union(&right);
+
+        assert!(left.iter().chain(right.iter()).all(|c| union.contains(&c)));
+        assert_eq!(union.intersect(&left), Some(left));
+        assert_eq!(left.intersect(&right), None);
+    }
+}
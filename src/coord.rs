@@ -3,7 +3,6 @@
 
 use crate::{direction, num, range, Direction};
 use anyhow::Result;
-use std::collections::HashSet;
 use std::str::FromStr;
 use std::{cmp, fmt, ops};
 
@@ -275,7 +274,7 @@ where
                 .map(|(x, y)| Coord::at(x, y)),
         ];
 
-        ret.into_iter().filter_map(|s| s)
+        ret.into_iter().flatten()
     }
 
     /// Returns an iterator over all the adjacent position.
@@ -391,13 +390,15 @@ where
                 .map(|(x, y)| Coord::at(x, y)),
         ];
 
-        ret.into_iter().filter_map(|s| s)
+        ret.into_iter().flatten()
     }
 }
 
 impl<I: Ord + Clone + fmt::Debug> Coord<I> {
-    /// Generate an iterator from a point to another.
-    /// The fonction will return an error if the starting point is before the ending point.
+    /// Generate an iterator from a point to another, line by line. See [crate::Rect] to do more
+    /// than iterating.
+    /// The fonction will return an error if the starting point is not the top left corner, see
+    /// [range::Range::between] to walk from any corner.
     /// ```
     /// use aoc::Coord;
     ///
@@ -447,27 +448,10 @@ where
     /// . . . # . . .
     /// . . . . . . .
     /// ```
+    ///
+    /// See also [Coord::manhattan_ring] if you don't need to store the coordinates.
     pub fn manhattan_coords_at_distance(&self, distance: I) -> Vec<Coord<I>> {
-        let mut ret = Vec::new();
-        let mut explored = HashSet::new();
-        let mut to_explore = vec![(self.clone() + Coord::at(I::zero(), distance))];
-
-        loop {
-            if let Some(current) = to_explore.pop() {
-                explored.insert(current);
-                ret.push(current);
-                to_explore.extend(
-                    current
-                        .diagonal()
-                        .filter(|coord| !explored.contains(coord))
-                        .filter(|coord| self.manhattan_distance_from(coord) == distance),
-                );
-            } else {
-                break;
-            }
-        }
-
-        ret
+        self.manhattan_ring(distance).collect()
     }
 
     /// Returns a `Vec` of `Coord` at a distance of exactly `distance` from the starting point.
@@ -482,27 +466,282 @@ where
     /// . # # # # # .
     /// . . . . . . .
     /// ```
+    ///
+    /// See also [Coord::chebyshev_ring] if you don't need to store the coordinates.
     pub fn chebyshev_coords_at_distance(&self, distance: I) -> Vec<Coord<I>> {
-        let mut ret = Vec::new();
-        let mut explored = HashSet::new();
-        let mut to_explore = vec![(self.clone() + Coord::at(I::zero(), distance))];
-
-        loop {
-            if let Some(current) = to_explore.pop() {
-                explored.insert(current);
-                ret.push(current);
-                to_explore.extend(
-                    current
-                        .manhattan_adjacent()
-                        .filter(|coord| !explored.contains(coord))
-                        .filter(|coord| self.chebyshev_distance_from(coord) == distance),
-                );
-            } else {
-                break;
+        self.chebyshev_ring(distance).collect()
+    }
+
+    /// Returns a lazy iterator over the `Coord`s at a manhattan distance of exactly `distance`
+    /// from `self`.
+    /// The coordinates are returned counter clockwise, starting on the right of `self`.
+    /// The coordinates that can't be represented by `I` are skipped.
+    ///
+    /// See also [Coord::manhattan_coords_at_distance], [Coord::chebyshev_ring].
+    /// # Example
+    /// ```
+    /// use aoc::Coord;
+    ///
+    /// let mut coords = Coord::<isize>::at(0, 0).manhattan_ring(1);
+    ///
+    /// assert_eq!(coords.next(), Some(Coord::at(1, 0)));
+    /// assert_eq!(coords.next(), Some(Coord::at(0, -1)));
+    /// assert_eq!(coords.next(), Some(Coord::at(-1, 0)));
+    /// assert_eq!(coords.next(), Some(Coord::at(0, 1)));
+    /// assert_eq!(coords.next(), None);
+    ///
+    /// assert_eq!(Coord::<usize>::at(0, 0).manhattan_ring(1).count(), 2);
+    /// ```
+    pub fn manhattan_ring(&self, distance: I) -> impl Iterator<Item = Coord<I>> {
+        Ring::new(*self, distance, Metric::Manhattan)
+    }
+
+    /// Returns a lazy iterator over the `Coord`s at a chebyshev distance of exactly `distance`
+    /// from `self`.
+    /// The coordinates are returned in the order of a square spiral, see [Coord::spiral].
+    /// The coordinates that can't be represented by `I` are skipped.
+    ///
+    /// See also [Coord::chebyshev_coords_at_distance], [Coord::manhattan_ring].
+    /// # Example
+    /// ```
+    /// use aoc::Coord;
+    ///
+    /// let mut coords = Coord::<isize>::at(0, 0).chebyshev_ring(1);
+    ///
+    /// assert_eq!(coords.next(), Some(Coord::at(1, 0)));
+    /// assert_eq!(coords.next(), Some(Coord::at(1, -1)));
+    /// assert_eq!(coords.next(), Some(Coord::at(0, -1)));
+    /// assert_eq!(coords.next(), Some(Coord::at(-1, -1)));
+    /// assert_eq!(coords.next(), Some(Coord::at(-1, 0)));
+    /// assert_eq!(coords.next(), Some(Coord::at(-1, 1)));
+    /// assert_eq!(coords.next(), Some(Coord::at(0, 1)));
+    /// assert_eq!(coords.next(), Some(Coord::at(1, 1)));
+    /// assert_eq!(coords.next(), None);
+    /// ```
+    pub fn chebyshev_ring(&self, distance: I) -> impl Iterator<Item = Coord<I>> {
+        Ring::new(*self, distance, Metric::Chebyshev)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Metric {
+    Manhattan,
+    Chebyshev,
+}
+
+/// Lazily walk the four sides of a ring, see [Coord::manhattan_ring] and [Coord::chebyshev_ring].
+#[derive(Debug, Clone)]
+struct Ring<I> {
+    center: Coord<I>,
+    distance: I,
+    metric: Metric,
+    side: usize,
+    step: I,
+}
+
+impl<I: num::Zero> Ring<I> {
+    fn new(center: Coord<I>, distance: I, metric: Metric) -> Self {
+        Self {
+            center,
+            distance,
+            metric,
+            side: 0,
+            step: I::zero(),
+        }
+    }
+}
+
+impl<I> Iterator for Ring<I>
+where
+    I: ops::Add<Output = I> + num::Zero + num::One + num::CheckedOp + Ord + Copy,
+{
+    type Item = Coord<I>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // `base + plus - minus` without going through a negative number
+        let offset = |base: I, plus: I, minus: I| (base + plus).checked_sub(minus);
+        let (x, y, k, one) = (self.center.x, self.center.y, self.distance, I::one());
+
+        if k == I::zero() {
+            self.side += 1;
+            return (self.side == 1).then_some(self.center);
+        }
+
+        while self.side < 4 {
+            let side_len = match self.metric {
+                Metric::Manhattan => k,
+                Metric::Chebyshev => k + k,
+            };
+            if self.step >= side_len {
+                self.side += 1;
+                self.step = I::zero();
+                continue;
             }
+            let s = self.step;
+            self.step = self.step + one;
+
+            let (x, y) = match (self.metric, self.side) {
+                (Metric::Manhattan, 0) => (offset(x, k, s), offset(y, I::zero(), s)),
+                (Metric::Manhattan, 1) => (offset(x, I::zero(), s), offset(y, s, k)),
+                (Metric::Manhattan, 2) => (offset(x, s, k), offset(y, s, I::zero())),
+                (Metric::Manhattan, _) => (offset(x, s, I::zero()), offset(y, k, s)),
+                (Metric::Chebyshev, 0) => (offset(x, k, I::zero()), offset(y, k, one + s)),
+                (Metric::Chebyshev, 1) => (offset(x, k, one + s), offset(y, I::zero(), k)),
+                (Metric::Chebyshev, 2) => (offset(x, I::zero(), k), offset(y, one + s, k)),
+                (Metric::Chebyshev, _) => (offset(x, one + s, k), offset(y, k, I::zero())),
+            };
+            if let Some((x, y)) = x.zip(y) {
+                return Some(Coord::at(x, y));
+            }
+        }
+
+        None
+    }
+}
+
+impl Coord<isize> {
+    /// Returns a lazy iterator over all the `Coord`s at a manhattan distance lower or equal to
+    /// `distance` from `self`, line by line.
+    ///
+    /// See also [ManhattanBall](crate::ManhattanBall), [Coord::manhattan_ring].
+    /// # Example
+    /// ```
+    /// use aoc::Coord;
+    ///
+    /// assert_eq!(Coord::at(4, 2).manhattan_coords_within(2).count(), 13);
+    /// assert!(Coord::at(4, 2).manhattan_coords_within(2).all(|coord| coord.manhattan_distance_from(&Coord::at(4, 2)) <= 2));
+    /// ```
+    pub fn manhattan_coords_within(&self, distance: isize) -> impl Iterator<Item = Coord<isize>> {
+        crate::ManhattanBall::new(*self, distance).iter()
+    }
+
+    /// Rotate the space by 45 degrees and scale it so every coordinate is still an integer,
+    /// `(x, y)` becomes `(x + y, x - y)`.
+    /// In this space the manhattan distance becomes a chebyshev distance, which means
+    /// a [ManhattanBall](crate::ManhattanBall) becomes a square.
+    ///
+    /// See also [Coord::from_rotated_axes].
+    /// # Example
+    /// ```
+    /// use aoc::Coord;
+    ///
+    /// let coord = Coord::at(3, 1);
+    ///
+    /// assert_eq!(coord.to_rotated_axes(), Coord::at(4, 2));
+    /// assert_eq!(Coord::from_rotated_axes(coord.to_rotated_axes()), Some(coord));
+    /// ```
+    pub fn to_rotated_axes(&self) -> Self {
+        Coord::at(self.x + self.y, self.x - self.y)
+    }
+
+    /// Convert back a coordinate from the rotated space, see [Coord::to_rotated_axes].
+    /// Returns `None` if the coordinate doesn't match any coordinate of the normal space, which
+    /// happens when `x + y` is odd.
+    pub fn from_rotated_axes(rotated: Self) -> Option<Self> {
+        ((rotated.x + rotated.y) % 2 == 0)
+            .then(|| Coord::at((rotated.x + rotated.y) / 2, (rotated.x - rotated.y) / 2))
+    }
+
+    /// Returns an infinite iterator over all the coordinates following a square spiral
+    /// centered on `self`.
+    /// The spiral starts on the right of `self` and then turns counter clockwise.
+    ///
+    /// ```text
+    /// 16 15 14 13 12
+    /// 17  4  3  2 11
+    /// 18  5  0  1 10
+    /// 19  6  7  8  9
+    /// 20 21 22 23 24
+    /// ```
+    ///
+    /// See also [Coord::from_spiral_index], [Coord::to_spiral_index].
+    /// # Example
+    /// ```
+    /// use aoc::Coord;
+    ///
+    /// let mut spiral = Coord::at(10, 10).spiral();
+    ///
+    /// assert_eq!(spiral.next(), Some(Coord::at(10, 10)));
+    /// assert_eq!(spiral.next(), Some(Coord::at(11, 10)));
+    /// assert_eq!(spiral.next(), Some(Coord::at(11, 9)));
+    /// assert_eq!(spiral.next(), Some(Coord::at(10, 9)));
+    /// assert_eq!(spiral.nth(20), Some(Coord::at(12, 12)));
+    /// ```
+    pub fn spiral(&self) -> impl Iterator<Item = Coord<isize>> {
+        let center = *self;
+        (0..).flat_map(move |distance| center.chebyshev_ring(distance))
+    }
+
+    /// Returns the coordinate at the position `index` of a square spiral centered on the origin
+    /// in O(1).
+    ///
+    /// See also [Coord::spiral], [Coord::to_spiral_index].
+    /// # Example
+    /// ```
+    /// use aoc::Coord;
+    ///
+    /// assert_eq!(Coord::from_spiral_index(0), Coord::at(0, 0));
+    /// assert_eq!(Coord::from_spiral_index(1), Coord::at(1, 0));
+    /// assert_eq!(Coord::from_spiral_index(8), Coord::at(1, 1));
+    /// assert_eq!(Coord::from_spiral_index(14), Coord::at(0, -2));
+    /// // The square 1024 of the 2017 day 3 is 31 steps away from the origin
+    /// assert_eq!(Coord::from_spiral_index(1023).manhattan_distance_from_base(), 31);
+    /// ```
+    pub fn from_spiral_index(index: usize) -> Self {
+        if index == 0 {
+            return Coord::at(0, 0);
+        }
+        let mut sqrt = (index as f64).sqrt() as usize;
+        // fix the rounding errors of the float on very big numbers
+        while sqrt * sqrt > index {
+            sqrt -= 1;
+        }
+        while (sqrt + 1) * (sqrt + 1) <= index {
+            sqrt += 1;
+        }
+
+        let k = sqrt.div_ceil(2);
+        let offset = index - (2 * k - 1) * (2 * k - 1);
+        let (side, s) = (offset / (2 * k), (offset % (2 * k)) as isize);
+        let k = k as isize;
+
+        match side {
+            0 => Coord::at(k, k - 1 - s),
+            1 => Coord::at(k - 1 - s, -k),
+            2 => Coord::at(-k, -k + 1 + s),
+            _ => Coord::at(-k + 1 + s, k),
         }
+    }
 
-        ret
+    /// Returns the position of the coordinate in a square spiral centered on the origin in O(1).
+    ///
+    /// See also [Coord::spiral], [Coord::from_spiral_index].
+    /// # Example
+    /// ```
+    /// use aoc::Coord;
+    ///
+    /// assert_eq!(Coord::at(0, 0).to_spiral_index(), 0);
+    /// assert_eq!(Coord::at(1, 0).to_spiral_index(), 1);
+    /// assert_eq!(Coord::at(-2, 2).to_spiral_index(), 20);
+    /// ```
+    pub fn to_spiral_index(&self) -> usize {
+        let k = self.chebyshev_distance_from_base();
+        if k == 0 {
+            return 0;
+        }
+        let Coord { x, y } = *self;
+
+        let (side, s) = if x == k && y < k {
+            (0, k - 1 - y)
+        } else if y == -k && x < k {
+            (1, k - 1 - x)
+        } else if x == -k && y > -k {
+            (2, y + k - 1)
+        } else {
+            (3, x + k - 1)
+        };
+
+        ((2 * k - 1) * (2 * k - 1) + side * 2 * k + s) as usize
     }
 }
 
@@ -571,6 +810,48 @@ where
     pub fn rotate_counter_clockwise_n(&self, n: usize) -> Self {
         (0..n).fold(self.clone(), |coord, _| coord.rotate_counter_clockwise())
     }
+
+    /// Negate the `x` component of the coordinate, this is a reflection across the vertical axis.
+    /// ```
+    /// use aoc::Coord;
+    ///
+    /// assert_eq!(Coord::<isize>::at(3, 1).reflect_x(), Coord::at(-3, 1));
+    /// ```
+    pub fn reflect_x(&self) -> Self {
+        Self::at(-self.x.clone(), self.y.clone())
+    }
+
+    /// Negate the `y` component of the coordinate, this is a reflection across the horizontal axis.
+    /// ```
+    /// use aoc::Coord;
+    ///
+    /// assert_eq!(Coord::<isize>::at(3, 1).reflect_y(), Coord::at(3, -1));
+    /// ```
+    pub fn reflect_y(&self) -> Self {
+        Self::at(self.x.clone(), -self.y.clone())
+    }
+}
+
+impl<I> Coord<I>
+where
+    I: ops::Neg<Output = I> + ops::Sub<Output = I> + ops::Add<Output = I> + Clone,
+{
+    /// Rotate the coordinate clockwise around a pivot by `quarter_turns` quarter of turn.
+    /// A negative number of quarter turns rotate the coordinate counter clockwise.
+    /// ```
+    /// use aoc::Coord;
+    ///
+    /// let pivot = Coord::<isize>::at(10, 10);
+    ///
+    /// assert_eq!(Coord::at(13, 11).rotate_around(pivot.clone(), 1), Coord::at(9, 13));
+    /// assert_eq!(Coord::at(13, 11).rotate_around(pivot.clone(), -1), Coord::at(11, 7));
+    /// assert_eq!(Coord::at(13, 11).rotate_around(pivot.clone(), 6), Coord::at(7, 9));
+    /// assert_eq!(pivot.rotate_around(pivot, 1), pivot);
+    /// ```
+    pub fn rotate_around(&self, pivot: Self, quarter_turns: isize) -> Self {
+        let relative = self.clone() - pivot.clone();
+        relative.rotate_clockwise_n(quarter_turns.rem_euclid(4) as usize) + pivot
+    }
 }
 
 impl<I: Ord> PartialOrd for Coord<I> {
@@ -641,7 +922,7 @@ where
     T: Clone,
 {
     fn from(t: &(T, T)) -> Self {
-        unsafe { std::mem::transmute::<_, &Coord<T>>(t) }.clone()
+        unsafe { std::mem::transmute::<&(T, T), &Coord<T>>(t) }.clone()
     }
 }
 
@@ -750,6 +1031,39 @@ where
     }
 }
 
+impl<I> std::ops::Add<direction::Direction8> for Coord<I>
+where
+    I: num::One + ops::Add<Output = I> + ops::Sub<Output = I>,
+{
+    type Output = Self;
+
+    /// Allow to add [Direction8](crate::Direction8)s to `Coord`.
+    ///
+    /// ```
+    /// use aoc::{Coord, Direction8};
+    ///
+    /// let coord = Coord::at(5, 5);
+    ///
+    /// assert_eq!(coord + Direction8::NorthEast, Coord::at(6, 4));
+    /// assert_eq!(coord + Direction8::SouthWest, Coord::at(4, 6));
+    /// assert_eq!(coord + Direction8::West, Coord::at(4, 5));
+    /// ```
+    fn add(self, dir: direction::Direction8) -> Self {
+        use direction::Direction8::*;
+        let x = match dir {
+            NorthEast | East | SouthEast => self.x + I::one(),
+            NorthWest | West | SouthWest => self.x - I::one(),
+            North | South => self.x,
+        };
+        let y = match dir {
+            SouthEast | South | SouthWest => self.y + I::one(),
+            NorthEast | North | NorthWest => self.y - I::one(),
+            East | West => self.y,
+        };
+        Self { x, y }
+    }
+}
+
 impl<I> std::ops::Add<crate::Movement> for Coord<I>
 where
     I: num::One + ops::Add<Output = I> + ops::Sub<Output = I>,
@@ -950,37 +1264,22 @@ where
 
 #[cfg(test)]
 mod tests {
+    use crate::iterator::BoundingBoxExt;
     use crate::Grid;
 
     use super::*;
 
     fn visualize(coords: impl IntoIterator<Item = Coord<isize>>) -> String {
-        let mut coords = coords.into_iter().collect::<Vec<_>>();
+        let coords = coords.into_iter().collect::<Vec<_>>();
+        let bbox = coords.iter().copied().bounding_box().unwrap();
 
-        let min_x = coords.iter().map(|coord| coord.x).min().unwrap();
-        let min_y = coords.iter().map(|coord| coord.y).min().unwrap();
-
-        if min_x <= 0 {
-            let inc = min_x.abs();
-            coords.iter_mut().for_each(|coord| coord.x += inc + 1);
-        }
-        if min_y <= 0 {
-            let inc = min_y.abs();
-            coords.iter_mut().for_each(|coord| coord.y += inc + 1);
-        }
-        let coords = coords
-            .into_iter()
-            .map(|Coord { x, y }| Coord::at(x as usize, y as usize))
-            .collect::<Vec<_>>();
-
-        let max_x = coords.iter().map(|coord| coord.x).max().unwrap();
-        let max_y = coords.iter().map(|coord| coord.y).max().unwrap();
-
-        let grid = Grid::<char>::with_dimension(max_x + 2, max_y + 2);
+        // keep an empty border around the coordinates
+        let grid =
+            Grid::<char>::with_dimension(bbox.width() as usize + 2, bbox.height() as usize + 2);
         let mut grid = grid.map(|_| '.');
 
         for coord in coords {
-            grid[coord] = '#';
+            grid[coord - bbox.min + 1] = '#';
         }
 
         format!("{}", grid)
@@ -1076,6 +1375,25 @@ mod tests {
         "###);
     }
 
+    #[test]
+    fn test_spiral_index() {
+        for (index, coord) in Coord::at(0, 0).spiral().take(10_000).enumerate() {
+            assert_eq!(Coord::from_spiral_index(index), coord);
+            assert_eq!(coord.to_spiral_index(), index);
+        }
+    }
+
+    #[test]
+    fn test_rings_on_unsigned() {
+        let coord = Coord::<usize>::at(1, 0);
+
+        assert_eq!(coord.chebyshev_ring(1).count(), 5);
+        assert_eq!(coord.manhattan_ring(2).count(), 4);
+        assert!(coord
+            .chebyshev_ring(3)
+            .all(|c| coord.chebyshev_distance_from(&c) == 3));
+    }
+
     #[test]
     fn test_chebyshev_coords_at_distance() {
         let coord = Coord::at(0, 0);
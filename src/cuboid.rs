@@ -0,0 +1,333 @@
+//! Define a [Cuboid], the 3D equivalent of a [crate::Rect], and a [CuboidSet] to keep track of
+//! the union of a bunch of cuboids.
+//!
+//! The 3D points are represented as `(x, y, z)` tuples, like in [crate::Rotation].
+
+use crate::{num, rect::subtract_along_axes};
+use anyhow::{ensure, Result};
+use std::{fmt, ops};
+
+/// An axis-aligned cuboid going from the `start` corner to the `end` corner.
+/// Both corners are included in the cuboid, thus a [Cuboid] is never empty.
+/// ```
+/// use aoc::Cuboid;
+///
+/// let cuboid = Cuboid::new((10, 10, 10), (12, 12, 12)).unwrap();
+///
+/// assert_eq!(cuboid.volume(), 27);
+/// assert!(cuboid.contains((11, 12, 10)));
+/// assert!(Cuboid::new((0, 1, 0), (1, 0, 1)).is_err());
+/// ```
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct Cuboid<I = isize> {
+    start: [I; 3],
+    end: [I; 3],
+}
+
+impl<I: Ord + Copy + fmt::Debug> Cuboid<I> {
+    /// Create a new cuboid, returns an error if `start` is after `end` on any axis.
+    pub fn new(start: (I, I, I), end: (I, I, I)) -> Result<Self> {
+        let (start, end) = ([start.0, start.1, start.2], [end.0, end.1, end.2]);
+        ensure!(
+            (0..3).all(|axis| start[axis] <= end[axis]),
+            "You can’t create a cuboid starting after the end: {:?} {:?}",
+            start,
+            end
+        );
+        Ok(Self { start, end })
+    }
+
+    /// The corner with the smallest coordinates.
+    pub fn start(&self) -> (I, I, I) {
+        (self.start[0], self.start[1], self.start[2])
+    }
+
+    /// The corner with the biggest coordinates.
+    pub fn end(&self) -> (I, I, I) {
+        (self.end[0], self.end[1], self.end[2])
+    }
+
+    /// Return `true` if the point is in the cuboid.
+    pub fn contains(&self, (x, y, z): (I, I, I)) -> bool {
+        [x, y, z]
+            .into_iter()
+            .enumerate()
+            .all(|(axis, value)| (self.start[axis]..=self.end[axis]).contains(&value))
+    }
+
+    /// Return the cuboid shared by both cuboids.
+    /// ```
+    /// use aoc::Cuboid;
+    ///
+    /// let left = Cuboid::new((0, 0, 0), (4, 4, 4)).unwrap();
+    /// let right = Cuboid::new((2, 3, -5), (6, 8, 1)).unwrap();
+    ///
+    /// assert_eq!(left.intersection(&right), Cuboid::new((2, 3, 0), (4, 4, 1)).ok());
+    /// assert_eq!(left.intersection(&Cuboid::new((0, 0, 5), (0, 0, 5)).unwrap()), None);
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut ret = *self;
+        for axis in 0..3 {
+            ret.start[axis] = self.start[axis].max(other.start[axis]);
+            ret.end[axis] = self.end[axis].min(other.end[axis]);
+            if ret.start[axis] > ret.end[axis] {
+                return None;
+            }
+        }
+        Some(ret)
+    }
+}
+
+impl<I> Cuboid<I>
+where
+    I: ops::Sub<Output = I> + ops::Add<Output = I> + num::One + Ord + Copy + fmt::Debug,
+{
+    /// The size of the cuboid along the `x` axis.
+    pub fn width(&self) -> I {
+        self.len(0)
+    }
+
+    /// The size of the cuboid along the `y` axis.
+    pub fn height(&self) -> I {
+        self.len(1)
+    }
+
+    /// The size of the cuboid along the `z` axis.
+    pub fn depth(&self) -> I {
+        self.len(2)
+    }
+
+    /// The number of points in the cuboid.
+    pub fn volume(&self) -> I
+    where
+        I: ops::Mul<Output = I>,
+    {
+        self.width() * self.height() * self.depth()
+    }
+
+    fn len(&self, axis: usize) -> I {
+        self.end[axis] - self.start[axis] + I::one()
+    }
+
+    /// Split the cuboid in two, the first part contains all the points with an `x` lower than `x`
+    /// and the second one all the other points. See [crate::Rect::split_at_x].
+    pub fn split_at_x(&self, x: I) -> (Option<Self>, Option<Self>) {
+        self.split_at(0, x)
+    }
+
+    /// Split the cuboid in two along the `y` axis, see [Cuboid::split_at_x].
+    pub fn split_at_y(&self, y: I) -> (Option<Self>, Option<Self>) {
+        self.split_at(1, y)
+    }
+
+    /// Split the cuboid in two along the `z` axis, see [Cuboid::split_at_x].
+    pub fn split_at_z(&self, z: I) -> (Option<Self>, Option<Self>) {
+        self.split_at(2, z)
+    }
+
+    fn split_at(&self, axis: usize, value: I) -> (Option<Self>, Option<Self>) {
+        let before = (value > self.start[axis]).then(|| {
+            let mut before = *self;
+            before.end[axis] = (value - I::one()).min(self.end[axis]);
+            before
+        });
+        let after = (value <= self.end[axis]).then(|| {
+            let mut after = *self;
+            after.start[axis] = value.max(self.start[axis]);
+            after
+        });
+        (before, after)
+    }
+
+    /// Remove `other` from the cuboid. The remaining points are returned as up to six non
+    /// overlapping cuboids, cut like in [crate::Rect::subtract].
+    /// ```
+    /// use aoc::Cuboid;
+    ///
+    /// let cuboid = Cuboid::new((0, 0, 0), (2, 2, 2)).unwrap();
+    /// let center = Cuboid::new((1, 1, 1), (1, 1, 1)).unwrap();
+    ///
+    /// let parts = cuboid.subtract(&center);
+    /// assert_eq!(parts.len(), 6);
+    /// assert_eq!(parts.iter().map(|part| part.volume()).sum::<isize>(), 26);
+    ///
+    /// assert_eq!(cuboid.subtract(&cuboid), vec![]);
+    /// ```
+    pub fn subtract(&self, other: &Self) -> Vec<Self>
+    where
+        I: num::CheckedOp,
+    {
+        let Some(hole) = self.intersection(other) else {
+            return vec![*self];
+        };
+        subtract_along_axes(
+            *self,
+            (0..3).map(|axis| (axis, hole.start[axis], hole.end[axis])),
+            Self::split_at,
+        )
+    }
+}
+
+/// The union of a bunch of [Cuboid]s, stored as a list of disjoint cuboids.
+/// ```
+/// use aoc::{Cuboid, CuboidSet};
+///
+/// let mut reactor = CuboidSet::new();
+/// reactor.add(Cuboid::new((10, 10, 10), (12, 12, 12)).unwrap());
+/// reactor.add(Cuboid::new((11, 11, 11), (13, 13, 13)).unwrap());
+/// reactor.remove(Cuboid::new((9, 9, 9), (11, 11, 11)).unwrap());
+/// reactor.add(Cuboid::new((10, 10, 10), (10, 10, 10)).unwrap());
+///
+/// assert_eq!(reactor.volume(), 39);
+/// assert!(reactor.contains((10, 10, 10)));
+/// assert!(!reactor.contains((11, 10, 10)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CuboidSet<I = isize> {
+    cuboids: Vec<Cuboid<I>>,
+}
+
+/// The default cuboid set is empty.
+impl<I> Default for CuboidSet<I> {
+    fn default() -> Self {
+        Self {
+            cuboids: Vec::new(),
+        }
+    }
+}
+
+impl<I> CuboidSet<I>
+where
+    I: ops::Sub<Output = I> + ops::Add<Output = I> + num::One + Ord + Copy + fmt::Debug,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add all the points of the cuboid to the set.
+    pub fn add(&mut self, cuboid: Cuboid<I>)
+    where
+        I: num::CheckedOp,
+    {
+        self.remove(cuboid);
+        self.cuboids.push(cuboid);
+    }
+
+    /// Remove all the points of the cuboid from the set.
+    pub fn remove(&mut self, cuboid: Cuboid<I>)
+    where
+        I: num::CheckedOp,
+    {
+        self.cuboids = self
+            .cuboids
+            .iter()
+            .flat_map(|current| current.subtract(&cuboid))
+            .collect();
+    }
+
+    /// Return `true` if the point is in one of the cuboids.
+    pub fn contains(&self, point: (I, I, I)) -> bool {
+        self.cuboids.iter().any(|cuboid| cuboid.contains(point))
+    }
+
+    /// Return the number of points in the set.
+    pub fn volume(&self) -> I
+    where
+        I: ops::Mul<Output = I> + num::Zero,
+    {
+        self.cuboids
+            .iter()
+            .fold(I::zero(), |acc, cuboid| acc + cuboid.volume())
+    }
+
+    /// Iterate over the disjoint cuboids making the set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &Cuboid<I>> {
+        self.cuboids.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cuboids.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::iproduct;
+    use std::collections::HashSet;
+
+    fn points(cuboid: &Cuboid<i32>) -> impl Iterator<Item = (i32, i32, i32)> {
+        let ((sx, sy, sz), (ex, ey, ez)) = (cuboid.start(), cuboid.end());
+        iproduct!(sx..=ex, sy..=ey, sz..=ez)
+    }
+
+    #[test]
+    fn subtract_is_disjoint() {
+        let cuboid = Cuboid::new((0, 0, 0), (4, 3, 2)).unwrap();
+
+        for (x, y, z) in iproduct!(-2..5, -2..4, -2..3) {
+            let other = Cuboid::new((x, y, z), (x + 2, y + 1, z + 2)).unwrap();
+            let parts = cuboid.subtract(&other);
+            assert!(parts.len() <= 6);
+
+            let mut seen = HashSet::new();
+            for point in parts.iter().flat_map(points) {
+                assert!(seen.insert(point), "{point:?} is in two parts");
+            }
+            let expected: HashSet<_> = points(&cuboid)
+                .filter(|point| !other.contains(*point))
+                .collect();
+            assert_eq!(seen, expected);
+        }
+    }
+
+    #[test]
+    fn reboot() {
+        let steps = [
+            (true, (-5, -5, -5), (5, 5, 5)),
+            (false, (0, 0, 0), (9, 9, 9)),
+            (true, (-2, 3, -8), (2, 8, -1)),
+            (false, (-1, -1, -1), (1, 1, 1)),
+            (true, (4, -9, 0), (9, -4, 3)),
+        ];
+        let mut set = CuboidSet::new();
+        let mut expected = HashSet::new();
+
+        for (on, start, end) in steps {
+            let cuboid = Cuboid::new(start, end).unwrap();
+            if on {
+                set.add(cuboid);
+                expected.extend(points(&cuboid));
+            } else {
+                set.remove(cuboid);
+                points(&cuboid).for_each(|point| {
+                    expected.remove(&point);
+                });
+            }
+            assert_eq!(set.volume() as usize, expected.len());
+        }
+        assert!(expected.iter().all(|point| set.contains(*point)));
+    }
+
+    #[test]
+    fn bounds_of_the_type() {
+        let cuboid = Cuboid::new((i8::MIN, i8::MIN, 0), (i8::MAX, i8::MAX, 1)).unwrap();
+        assert_eq!(cuboid.subtract(&cuboid), vec![]);
+
+        let hole = Cuboid::new((0, i8::MIN, 1), (i8::MAX, 0, 1)).unwrap();
+        assert_eq!(
+            cuboid.subtract(&hole),
+            vec![
+                Cuboid::new((i8::MIN, i8::MIN, 0), (-1, i8::MAX, 1)).unwrap(),
+                Cuboid::new((0, i8::MIN, 0), (i8::MAX, 0, 0)).unwrap(),
+                Cuboid::new((0, 1, 0), (i8::MAX, i8::MAX, 1)).unwrap(),
+            ]
+        );
+
+        let mut set = CuboidSet::new();
+        set.add(cuboid);
+        set.remove(hole);
+        assert!(set.contains((i8::MAX, i8::MAX, 1)));
+        assert!(!set.contains((i8::MAX, i8::MIN, 1)));
+    }
+}
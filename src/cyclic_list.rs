@@ -8,6 +8,12 @@ pub struct CyclicList<T> {
     len: usize,
 }
 
+impl<T> Default for CyclicList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> CyclicList<T> {
     /// Create a new empty [CyclicList].
     ///
@@ -598,6 +604,7 @@ impl<T> FromIterator<T> for CyclicList<T> {
     }
 }
 
+#[allow(dead_code)]
 pub struct Iter<'a, T: 'a> {
     list: CyclicList<T>,
     // The number of elements that have been returned. Once this reach the
@@ -617,7 +624,7 @@ impl<'a, T> Iterator for Iter<'a, T> {
             self.list.move_right();
             // we need to transmute the element since rust is not able to infer
             // that elements returned by the [CyclicList] have a lifetime of 'a
-            unsafe { std::mem::transmute(self.list.current()) }
+            unsafe { std::mem::transmute::<Option<&T>, Option<&'a T>>(self.list.current()) }
         }
     }
 
@@ -679,6 +686,7 @@ impl<T> Node<T> {
         }
     }
 
+    #[allow(clippy::boxed_local)]
     fn into_element(self: Box<Self>) -> T {
         self.element
     }
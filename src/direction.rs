@@ -1,13 +1,15 @@
 //! Enum to represent a direction on a grid
 
-use crate::Movement;
+use crate::{num, Movement};
 use anyhow::{bail, Error, Result};
 use std::convert::TryFrom;
-use std::ops::Add;
+use std::ops::{Add, Neg};
 
 use std::str::FromStr;
 
 /// Represent a direction.
+/// `Up`, `Left`, `Right` and `Down` are synonyms of the cardinal directions, see
+/// [Direction::canonical]. For the diagonals see [Direction8].
 #[derive(Debug, Hash, Clone, Copy, Eq, PartialEq)]
 pub enum Direction {
     North,
@@ -20,32 +22,168 @@ pub enum Direction {
     Down,
 }
 
+/// A relative turn, see [Direction::turn] and [Direction8::turn].
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq)]
+pub enum Turn {
+    Left,
+    Right,
+    Back,
+}
+
 impl Direction {
-    pub fn rotate_clockwise(self) -> Self {
+    /// The four cardinal directions in clockwise order.
+    pub const ALL4: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// Iterate over the four cardinal directions in clockwise order, see [Direction::ALL4].
+    pub fn all4() -> impl Iterator<Item = Self> {
+        Self::ALL4.into_iter()
+    }
+
+    /// Map the synonyms to their cardinal direction, `Up` becomes `North`, `Left` becomes
+    /// `West`, `Right` becomes `East` and `Down` becomes `South`.
+    /// ```
+    /// use aoc::Direction;
+    ///
+    /// assert_eq!(Direction::Up.canonical(), Direction::North);
+    /// assert_eq!(Direction::Right.canonical(), Direction::East);
+    /// assert_eq!(Direction::South.canonical(), Direction::South);
+    /// ```
+    pub fn canonical(self) -> Self {
         match self {
-            Direction::North => Direction::East,
-            Direction::West => Direction::North,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::Up => Direction::Right,
-            Direction::Left => Direction::Up,
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
+            Direction::North | Direction::Up => Direction::North,
+            Direction::West | Direction::Left => Direction::West,
+            Direction::East | Direction::Right => Direction::East,
+            Direction::South | Direction::Down => Direction::South,
         }
     }
 
-    pub fn rotate_anti_clockwise(self) -> Self {
-        match self {
-            Direction::North => Direction::West,
-            Direction::West => Direction::South,
-            Direction::East => Direction::North,
-            Direction::South => Direction::West,
-            Direction::Up => Direction::Left,
-            Direction::Left => Direction::South,
-            Direction::Right => Direction::North,
-            Direction::Down => Direction::East,
+    /// Return the direction pointing the other way, synonyms stay synonyms.
+    /// ```
+    /// use aoc::Direction;
+    ///
+    /// assert_eq!(Direction::North.opposite(), Direction::South);
+    /// assert_eq!(Direction::Left.opposite(), Direction::Right);
+    /// ```
+    pub fn opposite(self) -> Self {
+        self.rotate_n(2)
+    }
+
+    /// Return the direction we face after turning.
+    /// ```
+    /// use aoc::{Direction, Turn};
+    ///
+    /// assert_eq!(Direction::North.turn(Turn::Left), Direction::West);
+    /// assert_eq!(Direction::North.turn(Turn::Right), Direction::East);
+    /// assert_eq!(Direction::Up.turn(Turn::Back), Direction::Down);
+    /// ```
+    pub fn turn(self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => self.rotate_anti_clockwise(),
+            Turn::Right => self.rotate_clockwise(),
+            Turn::Back => self.opposite(),
         }
     }
+
+    /// Return `true` for the directions moving along the `x` axis.
+    pub fn is_horizontal(self) -> bool {
+        matches!(self.canonical(), Direction::East | Direction::West)
+    }
+
+    /// Return `true` for the directions moving along the `y` axis.
+    pub fn is_vertical(self) -> bool {
+        matches!(self.canonical(), Direction::North | Direction::South)
+    }
+
+    /// Return the [Coord](crate::Coord) to add to move one step in this direction.
+    /// The `y` axis grows toward the `South`.
+    /// ```
+    /// use aoc::{Coord, Direction};
+    ///
+    /// assert_eq!(Direction::North.to_coord_delta::<i32>(), Coord::at(0, -1));
+    /// assert_eq!(Direction::Right.to_coord_delta::<f64>(), Coord::at(1., 0.));
+    /// ```
+    pub fn to_coord_delta<I: num::Zero + num::One + Neg<Output = I>>(self) -> crate::Coord<I> {
+        let (x, y) = match self.canonical() {
+            Direction::North => (I::zero(), -I::one()),
+            Direction::East => (I::one(), I::zero()),
+            Direction::South => (I::zero(), I::one()),
+            _ => (-I::one(), I::zero()),
+        };
+        crate::Coord::at(x, y)
+    }
+
+    /// Build a direction from the [Coord](crate::Coord) added by one step, see
+    /// [Direction::to_coord_delta]. Returns `None` if it isn't a cardinal unit vector.
+    /// ```
+    /// use aoc::{Coord, Direction};
+    ///
+    /// assert_eq!(Direction::from_coord_delta(Coord::at(0, 1)), Some(Direction::South));
+    /// assert_eq!(Direction::from_coord_delta(Coord::at(1, 1)), None);
+    /// ```
+    pub fn from_coord_delta<I>(delta: crate::Coord<I>) -> Option<Self>
+    where
+        I: num::Zero + num::One + Neg<Output = I> + PartialEq,
+    {
+        Self::ALL4
+            .into_iter()
+            .find(|direction| direction.to_coord_delta::<I>() == delta)
+    }
+
+    /// Map the cardinal directions to their synonyms, the opposite of [Direction::canonical].
+    fn synonym(self) -> Self {
+        match self.canonical() {
+            Direction::North => Direction::Up,
+            Direction::West => Direction::Left,
+            Direction::East => Direction::Right,
+            _ => Direction::Down,
+        }
+    }
+
+    /// Rotate clockwise by `n` quarter turns, a negative `n` rotates anti clockwise.
+    /// The rotation is the one of [Coord::rotate_clockwise](crate::Coord::rotate_clockwise)
+    /// applied on [Direction::to_coord_delta], and the synonyms stay synonyms.
+    /// ```
+    /// use aoc::Direction;
+    ///
+    /// assert_eq!(Direction::North.rotate_n(1), Direction::East);
+    /// assert_eq!(Direction::North.rotate_n(-1), Direction::West);
+    /// assert_eq!(Direction::Up.rotate_n(6), Direction::Down);
+    /// ```
+    pub fn rotate_n(self, n: i32) -> Self {
+        let delta = self
+            .to_coord_delta::<i32>()
+            .rotate_clockwise_n(n.rem_euclid(4) as usize);
+        let rotated =
+            Self::from_coord_delta(delta).expect("A rotated unit vector is still a unit vector");
+
+        if self == self.canonical() {
+            rotated
+        } else {
+            rotated.synonym()
+        }
+    }
+
+    /// Rotate clockwise by a quarter turn.
+    /// ```
+    /// use aoc::Direction;
+    ///
+    /// assert_eq!(Direction::North.rotate_clockwise(), Direction::East);
+    /// assert_eq!(Direction::Left.rotate_clockwise(), Direction::Up);
+    /// ```
+    pub fn rotate_clockwise(self) -> Self {
+        self.rotate_n(1)
+    }
+
+    /// Rotate anti clockwise by a quarter turn.
+    /// ```
+    /// use aoc::Direction;
+    ///
+    /// assert_eq!(Direction::South.rotate_anti_clockwise(), Direction::East);
+    /// assert_eq!(Direction::Left.rotate_anti_clockwise(), Direction::Down);
+    /// ```
+    pub fn rotate_anti_clockwise(self) -> Self {
+        self.rotate_n(-1)
+    }
 }
 
 /// The default direction is the `North`
@@ -77,6 +215,7 @@ impl TryFrom<Movement> for Direction {
     /// - `East`
     /// - `South`
     /// - `West`
+    ///
     /// An error will be throw if it's either of the following variation:
     /// - `Forward`
     /// - `Right`
@@ -138,3 +277,317 @@ impl FromStr for Direction {
         })
     }
 }
+
+/// Represent one of the eight directions of a compass, the four cardinal directions and the
+/// diagonals in between.
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// The eight directions in clockwise order, starting at the `North`.
+    pub const ALL8: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    /// The four cardinal directions in clockwise order.
+    pub const ALL4: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// The four diagonals in clockwise order.
+    pub const DIAGONALS: [Self; 4] = [
+        Self::NorthEast,
+        Self::SouthEast,
+        Self::SouthWest,
+        Self::NorthWest,
+    ];
+
+    /// Iterate over the eight directions in clockwise order, see [Direction8::ALL8].
+    pub fn all8() -> impl Iterator<Item = Self> {
+        Self::ALL8.into_iter()
+    }
+
+    /// Iterate over the four cardinal directions in clockwise order, see [Direction8::ALL4].
+    pub fn all4() -> impl Iterator<Item = Self> {
+        Self::ALL4.into_iter()
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Rotate clockwise by an eighth of a turn.
+    /// ```
+    /// use aoc::Direction8;
+    ///
+    /// assert_eq!(Direction8::North.rotate_clockwise(), Direction8::NorthEast);
+    /// assert_eq!(Direction8::NorthWest.rotate_clockwise(), Direction8::North);
+    /// ```
+    pub fn rotate_clockwise(self) -> Self {
+        Self::ALL8[(self.index() + 1) % 8]
+    }
+
+    /// Rotate anti clockwise by an eighth of a turn.
+    pub fn rotate_anti_clockwise(self) -> Self {
+        Self::ALL8[(self.index() + 7) % 8]
+    }
+
+    /// Return the direction pointing the other way.
+    pub fn opposite(self) -> Self {
+        Self::ALL8[(self.index() + 4) % 8]
+    }
+
+    /// Return the direction we face after turning by a quarter (or a half) of turn.
+    /// ```
+    /// use aoc::{Direction8, Turn};
+    ///
+    /// assert_eq!(Direction8::NorthEast.turn(Turn::Left), Direction8::NorthWest);
+    /// assert_eq!(Direction8::NorthEast.turn(Turn::Right), Direction8::SouthEast);
+    /// assert_eq!(Direction8::NorthEast.turn(Turn::Back), Direction8::SouthWest);
+    /// ```
+    pub fn turn(self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => Self::ALL8[(self.index() + 6) % 8],
+            Turn::Right => Self::ALL8[(self.index() + 2) % 8],
+            Turn::Back => self.opposite(),
+        }
+    }
+
+    /// Return `true` for the directions only moving along the `x` axis.
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Self::East | Self::West)
+    }
+
+    /// Return `true` for the directions only moving along the `y` axis.
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Self::North | Self::South)
+    }
+
+    /// Return `true` for the directions moving along both axes.
+    pub fn is_diagonal(self) -> bool {
+        !self.is_horizontal() && !self.is_vertical()
+    }
+
+    /// Return the [Coord](crate::Coord) to add to move one step in this direction.
+    /// The `y` axis grows toward the `South`.
+    /// ```
+    /// use aoc::{Coord, Direction8};
+    ///
+    /// assert_eq!(Direction8::NorthEast.to_coord_delta::<isize>(), Coord::at(1, -1));
+    /// assert_eq!(Direction8::SouthWest.to_coord_delta::<isize>(), Coord::at(-1, 1));
+    /// ```
+    pub fn to_coord_delta<I: num::Zero + num::One + Neg<Output = I>>(self) -> crate::Coord<I> {
+        let x = match self {
+            Self::NorthEast | Self::East | Self::SouthEast => I::one(),
+            Self::NorthWest | Self::West | Self::SouthWest => -I::one(),
+            Self::North | Self::South => I::zero(),
+        };
+        let y = match self {
+            Self::SouthEast | Self::South | Self::SouthWest => I::one(),
+            Self::NorthEast | Self::North | Self::NorthWest => -I::one(),
+            Self::East | Self::West => I::zero(),
+        };
+        crate::Coord::at(x, y)
+    }
+}
+
+/// The default direction is the `North`
+impl Default for Direction8 {
+    fn default() -> Self {
+        Self::North
+    }
+}
+
+impl From<Direction> for Direction8 {
+    /// Convert a [Direction] to its cardinal direction.
+    fn from(direction: Direction) -> Self {
+        match direction.canonical() {
+            Direction::North => Self::North,
+            Direction::East => Self::East,
+            Direction::South => Self::South,
+            _ => Self::West,
+        }
+    }
+}
+
+impl TryFrom<Direction8> for Direction {
+    type Error = anyhow::Error;
+
+    /// Convert a cardinal [Direction8] to a [Direction], the diagonals can't be converted.
+    /// ```
+    /// use aoc::{Direction, Direction8};
+    ///
+    /// assert_eq!(Direction::try_from(Direction8::West).unwrap(), Direction::West);
+    /// assert!(Direction::try_from(Direction8::NorthWest).is_err());
+    /// ```
+    fn try_from(direction: Direction8) -> Result<Self> {
+        Ok(match direction {
+            Direction8::North => Direction::North,
+            Direction8::East => Direction::East,
+            Direction8::South => Direction::South,
+            Direction8::West => Direction::West,
+            d => bail!("Can't convert the diagonal {:?} into a Direction", d),
+        })
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = Error;
+
+    /// Generate a `Direction8` from a string. The cardinal directions accept everything
+    /// [Direction] accepts, the diagonals accept their initials (`"ne"`) and their full names
+    /// with or without a separator (`"north-east"`, `"north east"`, `"northeast"`).
+    /// ```
+    /// use aoc::Direction8;
+    ///
+    /// assert_eq!("NE".parse::<Direction8>().unwrap(), Direction8::NorthEast);
+    /// assert_eq!("south-west".parse::<Direction8>().unwrap(), Direction8::SouthWest);
+    /// assert_eq!("^".parse::<Direction8>().unwrap(), Direction8::North);
+    /// ```
+    fn from_str(s: &str) -> Result<Self> {
+        let s: String = s
+            .trim()
+            .to_lowercase()
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' '))
+            .collect();
+        Ok(match s.as_str() {
+            "ne" | "northeast" => Self::NorthEast,
+            "se" | "southeast" => Self::SouthEast,
+            "sw" | "southwest" => Self::SouthWest,
+            "nw" | "northwest" => Self::NorthWest,
+            s => s.parse::<Direction>()?.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    #[test]
+    fn compass_is_consistent() {
+        for direction in Direction8::all8() {
+            let delta = direction.to_coord_delta::<isize>();
+            assert_eq!(
+                direction.opposite().to_coord_delta(),
+                Coord::at(-delta.x, -delta.y)
+            );
+            assert_eq!(
+                direction.turn(Turn::Right).to_coord_delta(),
+                delta.rotate_clockwise()
+            );
+            assert_eq!(direction.turn(Turn::Left).turn(Turn::Right), direction);
+            assert_eq!(
+                direction.rotate_clockwise().rotate_anti_clockwise(),
+                direction
+            );
+            assert_eq!(
+                direction.is_diagonal(),
+                Direction8::DIAGONALS.contains(&direction)
+            );
+        }
+
+        for direction in Direction::all4() {
+            let delta = direction.to_coord_delta::<isize>();
+            assert_eq!(Direction8::from(direction).to_coord_delta(), delta);
+            assert_eq!(
+                direction.turn(Turn::Right).to_coord_delta(),
+                delta.rotate_clockwise()
+            );
+            assert_eq!(direction.turn(Turn::Left).turn(Turn::Right), direction);
+            assert_eq!(direction.is_horizontal(), delta.y == 0);
+            assert_eq!(direction.is_vertical(), delta.x == 0);
+            assert_eq!(
+                Direction::try_from(Direction8::from(direction)).unwrap(),
+                direction
+            );
+        }
+    }
+
+    const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::West,
+        Direction::East,
+        Direction::South,
+        Direction::Up,
+        Direction::Left,
+        Direction::Right,
+        Direction::Down,
+    ];
+
+    #[test]
+    fn rotations_cancel_out() {
+        for direction in ALL {
+            assert_eq!(
+                direction.rotate_anti_clockwise().rotate_clockwise(),
+                direction
+            );
+            assert_eq!(
+                direction.rotate_clockwise().rotate_anti_clockwise(),
+                direction
+            );
+            assert_eq!(
+                (0..4).fold(direction, |d, _| d.rotate_clockwise()),
+                direction
+            );
+            assert_eq!(
+                (0..4).fold(direction, |d, _| d.rotate_anti_clockwise()),
+                direction
+            );
+            assert_eq!(direction.rotate_n(4), direction);
+            assert_eq!(direction.rotate_n(0), direction);
+        }
+    }
+
+    #[test]
+    fn rotations_match_coord() {
+        for (direction, n) in itertools::iproduct!(ALL, -9..9) {
+            let rotated = direction.rotate_n(n);
+            assert_eq!(
+                rotated.to_coord_delta::<i32>(),
+                direction
+                    .to_coord_delta::<i32>()
+                    .rotate_clockwise_n(n.rem_euclid(4) as usize)
+            );
+            assert_eq!(
+                rotated == rotated.canonical(),
+                direction == direction.canonical()
+            );
+            assert_eq!(rotated.rotate_n(-n), direction);
+        }
+    }
+
+    #[test]
+    fn rotate_anti_clockwise() {
+        let rotated = ALL.map(Direction::rotate_anti_clockwise);
+        assert_eq!(
+            rotated,
+            [
+                Direction::West,
+                Direction::South,
+                Direction::North,
+                Direction::East,
+                Direction::Left,
+                Direction::Down,
+                Direction::Up,
+                Direction::Right,
+            ]
+        );
+    }
+}
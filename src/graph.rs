@@ -1,11 +1,39 @@
+use crate::{Coord, Direction8, Grid};
+use anyhow::{anyhow, ensure, Context, Result};
+use rayon::prelude::*;
 use std::{
     cmp::Reverse,
-    collections::{HashMap, HashSet},
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
     hash::Hash,
     marker::PhantomData,
 };
 
-pub type Id = usize;
+/// A handle on a node of a [Graph], returned by [Graph::insert_value].
+///
+/// The ids stay valid until their node is deleted or the graph is compacted (see
+/// [Graph::compact]), after that the graph ignores them instead of returning another node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Id {
+    index: usize,
+    generation: u32,
+}
+
+impl Id {
+    /// The position of the node in the graph, to index the dense matrices like
+    /// [Graph::floyd_warshall] or [Graph::all_pairs_bfs].
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// A step of a depth-first search, see [Graph::dfs_events].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DfsEvent {
+    /// The node and its depth, emitted before exploring its neighbours.
+    Pre(Id, usize),
+    /// The node and its depth, emitted once all its neighbours have been explored.
+    Post(Id, usize),
+}
 
 #[derive(Debug)]
 pub enum Directed {}
@@ -13,39 +41,279 @@ pub enum Directed {}
 #[derive(Debug)]
 pub enum Undirected {}
 
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::Directed {}
+    impl Sealed for super::Undirected {}
+}
+
+/// The kind of a [Graph], either [Directed] or [Undirected].
+pub trait GraphKind: private::Sealed {
+    /// Whether an edge only goes from its first node to its second one.
+    const DIRECTED: bool;
+}
+
+impl GraphKind for Directed {
+    const DIRECTED: bool = true;
+}
+
+impl GraphKind for Undirected {
+    const DIRECTED: bool = false;
+}
+
+/// The edges to remove to split an undirected [Graph] in two, see [Graph::min_cut].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCut {
+    /// The edges as sorted `(smallest id, biggest id)` pairs.
+    pub edges: Vec<(Id, Id)>,
+    /// The number of nodes on the smallest and biggest sides of the cut.
+    pub sizes: (usize, usize),
+}
+
+/// A set of node positions stored one bit per node, see [Graph::longest_simple_path_with].
+#[derive(Debug, Clone)]
+struct Visited(Vec<u64>);
+
+impl Visited {
+    fn new(len: usize) -> Self {
+        Self(vec![0; len.div_ceil(64)])
+    }
+
+    /// Returns `false` if the position was already in the set.
+    fn insert(&mut self, position: usize) -> bool {
+        let (word, bit) = (position / 64, 1 << (position % 64));
+        let inserted = self.0[word] & bit == 0;
+        self.0[word] |= bit;
+        inserted
+    }
+
+    fn remove(&mut self, position: usize) {
+        self.0[position / 64] &= !(1 << (position % 64));
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Graph<Value, Edge = (), Kind = Undirected> {
     nodes_ids: HashMap<Value, Id>,
     nodes: Vec<Option<Value>>,
     edges: Vec<Vec<(Id, Edge)>>,
+    /// `predecessors[to]` holds every `from` with an edge to `to`, once per edge. Only the directed
+    /// graphs fill it, the edges of an undirected node already are its predecessors.
+    predecessors: Vec<Vec<Id>>,
+    /// Bumped on every compaction to invalidate the previous ids.
+    generation: u32,
     kind: PhantomData<Kind>,
 }
 
+impl<Value, Edge, Kind> Graph<Value, Edge, Kind> {
+    fn empty() -> Self {
+        Self {
+            nodes_ids: HashMap::new(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            predecessors: Vec::new(),
+            generation: 0,
+            kind: PhantomData,
+        }
+    }
+
+    fn id(&self, index: usize) -> Id {
+        Id {
+            index,
+            generation: self.generation,
+        }
+    }
+
+    /// The position of the node of `id`, or `None` if the node was deleted or the id comes from
+    /// before a compaction.
+    fn index(&self, id: Id) -> Option<usize> {
+        (id.generation == self.generation && self.nodes.get(id.index)?.is_some())
+            .then_some(id.index)
+    }
+
+    fn expect_index(&self, id: Id) -> usize {
+        self.index(id)
+            .unwrap_or_else(|| panic!("{id:?} doesn't point to a node of the graph"))
+    }
+
+    /// O(1)
+    ///
+    /// The number of nodes in the graph, the deleted nodes aren't counted.
+    pub fn node_count(&self) -> usize {
+        self.nodes_ids.len()
+    }
+
+    /// The ids of the nodes that haven't been deleted.
+    fn live_ids(&self) -> impl Iterator<Item = Id> + '_ {
+        (0..self.nodes.len())
+            .filter(|index| self.nodes[*index].is_some())
+            .map(|index| self.id(index))
+    }
+
+    /// Remove one occurrence of `from` in the predecessors of the node at `to`.
+    fn forget_predecessor(&mut self, to: usize, from: Id) {
+        if let Some(position) = self.predecessors[to].iter().position(|id| *id == from) {
+            self.predecessors[to].remove(position);
+        }
+    }
+
+    /// Breadth-first search, O(nodes + edges)
+    ///
+    /// Iterate over the nodes reachable from `start` with their distance in edges, nearest
+    /// first. Nothing is yielded if `start` is stale, see [Id].
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_directed();
+    /// let ids: Vec<_> = (0..5).map(|value| graph.insert_value(value)).collect();
+    /// for (from, to) in [(0, 1), (1, 2), (0, 3), (3, 2), (4, 0)] {
+    ///     graph.create_edge(ids[from], ids[to]);
+    /// }
+    ///
+    /// let depths: Vec<_> = graph.bfs(ids[0]).map(|(id, depth)| (id.index(), depth)).collect();
+    /// assert_eq!(depths, vec![(0, 0), (1, 1), (3, 1), (2, 2)]);
+    /// ```
+    pub fn bfs(&self, start: Id) -> impl Iterator<Item = (Id, usize)> + '_ {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut to_explore = VecDeque::new();
+        if let Some(index) = self.index(start) {
+            seen[index] = true;
+            to_explore.push_back((start, 0));
+        }
+        std::iter::from_fn(move || {
+            let (current, depth) = to_explore.pop_front()?;
+            for (id, _) in &self.edges[current.index] {
+                if !seen[id.index] {
+                    seen[id.index] = true;
+                    to_explore.push_back((*id, depth + 1));
+                }
+            }
+            Some((current, depth))
+        })
+    }
+
+    /// Depth-first search, O(nodes + edges)
+    ///
+    /// Iterate over the nodes reachable from `start` in pre-order, with their depth in the
+    /// search tree. The neighbours are explored in the order of their edges.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_directed();
+    /// let ids: Vec<_> = (0..5).map(|value| graph.insert_value(value)).collect();
+    /// for (from, to) in [(0, 1), (1, 2), (0, 3), (3, 2), (4, 0)] {
+    ///     graph.create_edge(ids[from], ids[to]);
+    /// }
+    ///
+    /// let depths: Vec<_> = graph.dfs(ids[0]).map(|(id, depth)| (id.index(), depth)).collect();
+    /// assert_eq!(depths, vec![(0, 0), (1, 1), (2, 2), (3, 1)]);
+    /// ```
+    pub fn dfs(&self, start: Id) -> impl Iterator<Item = (Id, usize)> + '_ {
+        self.dfs_events(start).filter_map(|event| match event {
+            DfsEvent::Pre(id, depth) => Some((id, depth)),
+            DfsEvent::Post(..) => None,
+        })
+    }
+
+    /// Depth-first search, O(nodes + edges)
+    ///
+    /// Same as [Graph::dfs], but every node is also reported once all its neighbours have been
+    /// explored, this gives the post-order.
+    /// ```
+    /// use aoc::{DfsEvent, Graph};
+    ///
+    /// let mut graph = Graph::new_undirected();
+    /// let a = graph.insert_value("a");
+    /// let b = graph.insert_value("b");
+    /// let c = graph.insert_value("c");
+    /// graph.create_edge(a, b);
+    /// graph.create_edge(a, c);
+    ///
+    /// let events: Vec<_> = graph.dfs_events(a).collect();
+    /// assert_eq!(
+    ///     events,
+    ///     vec![
+    ///         DfsEvent::Pre(a, 0),
+    ///         DfsEvent::Pre(b, 1),
+    ///         DfsEvent::Post(b, 1),
+    ///         DfsEvent::Pre(c, 1),
+    ///         DfsEvent::Post(c, 1),
+    ///         DfsEvent::Post(a, 0),
+    ///     ]
+    /// );
+    /// ```
+    pub fn dfs_events(&self, start: Id) -> impl Iterator<Item = DfsEvent> + '_ {
+        let mut seen = vec![false; self.nodes.len()];
+        // The nodes being explored with the position of the next edge to follow, the start is
+        // reported on the first call.
+        let mut call_stack: Vec<(Id, usize)> = Vec::new();
+        let mut start = self.index(start).map(|index| {
+            seen[index] = true;
+            start
+        });
+        std::iter::from_fn(move || {
+            if let Some(start) = start.take() {
+                call_stack.push((start, 0));
+                return Some(DfsEvent::Pre(start, 0));
+            }
+            loop {
+                let depth = call_stack.len().checked_sub(1)?;
+                let (node, position) = call_stack.last_mut()?;
+                let node = *node;
+                let Some((next, _)) = self.edges[node.index].get(*position) else {
+                    call_stack.pop();
+                    return Some(DfsEvent::Post(node, depth));
+                };
+                *position += 1;
+                if !seen[next.index] {
+                    seen[next.index] = true;
+                    call_stack.push((*next, 0));
+                    return Some(DfsEvent::Pre(*next, depth + 1));
+                }
+            }
+        })
+    }
+
+    /// O(nodes + edges)
+    ///
+    /// The nodes reachable from `start`, `start` included.
+    pub fn reachable_from(&self, start: Id) -> HashSet<Id> {
+        self.bfs(start).map(|(id, _)| id).collect()
+    }
+}
+
 impl<Value, Edge, Kind> Graph<Value, Edge, Kind>
 where
     Value: Clone + PartialEq + Eq + Hash + std::fmt::Debug,
 {
     /// O(1)
     pub fn insert_value(&mut self, value: Value) -> Id {
-        if let Some(pos) = self.nodes_ids.get(&value) {
-            *pos
+        if let Some(id) = self.nodes_ids.get(&value) {
+            *id
         } else {
-            let pos = self.nodes.len();
-            self.nodes_ids.insert(value.clone(), pos);
+            let id = self.id(self.nodes.len());
+            self.nodes_ids.insert(value.clone(), id);
             self.nodes.push(Some(value));
             self.edges.push(Vec::new());
-            pos
+            self.predecessors.push(Vec::new());
+            id
         }
     }
 
     /// O(1)
+    ///
+    /// Returns `None` if the node was deleted or the id is stale, see [Id].
     pub fn get_value(&self, id: Id) -> Option<&Value> {
-        self.nodes[id].as_ref()
+        self.nodes[self.index(id)?].as_ref()
     }
 
     /// O(1)
+    ///
+    /// Returns `None` if the node was deleted or the id is stale, see [Id].
     pub fn get_value_mut(&mut self, id: Id) -> Option<&mut Value> {
-        self.nodes[id].as_mut()
+        let index = self.index(id)?;
+        self.nodes[index].as_mut()
     }
 
     /// O(1)
@@ -54,68 +322,461 @@ where
     }
 
     /// O(1)
-    pub fn get_edges(&self, id: Id) -> &[(Id, Edge)] {
-        &self.edges[id]
+    ///
+    /// Returns `None` if the node was deleted or the id is stale, see [Id].
+    pub fn get_edges(&self, id: Id) -> Option<&[(Id, Edge)]> {
+        Some(&self.edges[self.index(id)?])
     }
 
-    /// O(edges)
-    pub fn delete_value(&mut self, id: Id) -> bool {
-        debug_assert!(id < self.nodes.len());
-
-        let value = std::mem::take(&mut self.nodes[id]);
-        if value.is_none() {
+    /// O(edges of the node and of its neighbours)
+    ///
+    /// Returns `false` if the node was already deleted or the id is stale, see [Id].
+    pub fn delete_value(&mut self, id: Id) -> bool
+    where
+        Kind: GraphKind,
+    {
+        let Some(index) = self.index(id) else {
             return false;
-        }
-        let value = value.unwrap();
+        };
+        let value = self.nodes[index].take().expect("The node exists");
 
         self.nodes_ids.remove(&value);
-        self.edges[id].clear();
-        for edge in &mut self.edges {
-            edge.retain(|(i, _)| *i != id);
+        for (to, _) in std::mem::take(&mut self.edges[index]) {
+            if Kind::DIRECTED {
+                self.predecessors[to.index].retain(|from| *from != id);
+            } else {
+                self.edges[to.index].retain(|(other, _)| *other != id);
+            }
+        }
+        for from in std::mem::take(&mut self.predecessors[index]) {
+            self.edges[from.index].retain(|(to, _)| *to != id);
         }
 
         true
     }
 
     /// O(edges)
-    pub fn delete_by_value(&mut self, value: &Value) -> bool {
-        self.delete_value(self.get_id(value).unwrap())
+    ///
+    /// Returns `false` if there was no node with this value.
+    pub fn delete_by_value(&mut self, value: &Value) -> bool
+    where
+        Kind: GraphKind,
+    {
+        self.get_id(value).is_some_and(|id| self.delete_value(id))
+    }
+
+    /// O(nodes + edges)
+    ///
+    /// Drop the space left by the deleted nodes. The remaining nodes are renumbered in the same
+    /// order and all the previous ids become stale, the returned map gives the new id of every
+    /// remaining node.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_undirected();
+    /// let a = graph.insert_value("a");
+    /// let b = graph.insert_value("b");
+    /// let c = graph.insert_value("c");
+    /// graph.create_edge(a, c);
+    /// graph.delete_value(b);
+    ///
+    /// let new_ids = graph.compact();
+    /// assert_eq!(graph.get_value(a), None);
+    /// assert_eq!(graph.get_value(new_ids[&a]), Some(&"a"));
+    /// assert_eq!(new_ids[&c].index(), 1);
+    /// assert_eq!(graph.get_edges(new_ids[&a]), Some(&[(new_ids[&c], ())][..]));
+    /// assert!(!new_ids.contains_key(&b));
+    /// ```
+    pub fn compact(&mut self) -> HashMap<Id, Id> {
+        let old_ids: Vec<Id> = self.live_ids().collect();
+        let generation = self.generation + 1;
+        let new_ids: HashMap<Id, Id> = old_ids
+            .iter()
+            .enumerate()
+            .map(|(index, old)| (*old, Id { index, generation }))
+            .collect();
+
+        let mut nodes = std::mem::take(&mut self.nodes);
+        let mut edges = std::mem::take(&mut self.edges);
+        let predecessors = std::mem::take(&mut self.predecessors);
+        for old in &old_ids {
+            self.nodes.push(nodes[old.index].take());
+            self.edges.push(
+                std::mem::take(&mut edges[old.index])
+                    .into_iter()
+                    .map(|(to, edge)| (new_ids[&to], edge))
+                    .collect(),
+            );
+            self.predecessors.push(
+                predecessors[old.index]
+                    .iter()
+                    .map(|from| new_ids[from])
+                    .collect(),
+            );
+        }
+        for id in self.nodes_ids.values_mut() {
+            *id = new_ids[id];
+        }
+        self.generation = generation;
+        new_ids
     }
 
-    /// Dijkstra
+    /// The number of edges on the shortest path from `start` to `end`, see
+    /// [Graph::shortest_path_with] to take the edges data into account.
     pub fn distance_between(&self, start: Id, end: Id) -> Option<usize> {
+        self.shortest_path_with(start, end, |_, _, _| 1)
+            .map(|(distance, _)| distance as usize)
+    }
+
+    /// Dijkstra, O((nodes + edges) log nodes)
+    ///
+    /// Return the cost of the cheapest path from `start` to `end` and the nodes along this path,
+    /// `start` and `end` included. The cost of every edge is given by `weight(from, to, edge)`,
+    /// the paths whose cost overflows a `u64` are ignored.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_directed();
+    /// let a = graph.insert_value("a");
+    /// let b = graph.insert_value("b");
+    /// let c = graph.insert_value("c");
+    /// graph.create_edge_with_data(a, b, "a very long road");
+    /// graph.create_edge_with_data(a, c, "short");
+    /// graph.create_edge_with_data(c, b, "short");
+    ///
+    /// let weight = |_, _, edge: &&str| edge.len() as u64;
+    /// assert_eq!(graph.shortest_path_with(a, b, weight), Some((10, vec![a, c, b])));
+    /// assert_eq!(graph.shortest_path_with(b, a, weight), None);
+    /// ```
+    pub fn shortest_path_with(
+        &self,
+        start: Id,
+        end: Id,
+        weight: impl Fn(Id, Id, &Edge) -> u64,
+    ) -> Option<(u64, Vec<Id>)> {
+        let (distances, previous) = self.dijkstra(start, Some(end), weight);
+        let distance = *distances.get(&end)?;
+
+        let mut path = vec![end];
+        while let Some(id) = previous.get(path.last().expect("The path is never empty")) {
+            path.push(*id);
+        }
+        path.reverse();
+        Some((distance, path))
+    }
+
+    /// Return the cost of the cheapest path from `start` to every node reachable from it,
+    /// see [Graph::shortest_path_with].
+    pub fn distances_from_with(
+        &self,
+        start: Id,
+        weight: impl Fn(Id, Id, &Edge) -> u64,
+    ) -> HashMap<Id, u64> {
+        self.dijkstra(start, None, weight).0
+    }
+
+    /// Explore the graph from `start` until `end` is reached, or the whole graph if there is no
+    /// `end`. Returns the distances and the previous node on the cheapest path of every node
+    /// explored.
+    fn dijkstra(
+        &self,
+        start: Id,
+        end: Option<Id>,
+        weight: impl Fn(Id, Id, &Edge) -> u64,
+    ) -> (HashMap<Id, u64>, HashMap<Id, Id>) {
+        if self.index(start).is_none() {
+            return (HashMap::new(), HashMap::new());
+        }
+        let mut distances = HashMap::from([(start, 0)]);
+        let mut previous = HashMap::new();
         let mut explored = HashSet::new();
-        let mut to_explore = vec![(start, 0)];
+        let mut to_explore = BinaryHeap::from([Reverse((0_u64, start))]);
 
-        loop {
-            to_explore.sort_unstable_by(|(_, left), (_, right)| Reverse(left).cmp(&Reverse(right)));
-            if let Some((current, distance)) = to_explore.pop() {
-                if current == end {
-                    return Some(distance);
+        while let Some(Reverse((distance, current))) = to_explore.pop() {
+            if !explored.insert(current) {
+                continue;
+            }
+            if Some(current) == end {
+                break;
+            }
+            for (id, edge) in &self.edges[current.index] {
+                let Some(distance) = distance.checked_add(weight(current, *id, edge)) else {
+                    continue;
+                };
+                if distances.get(id).is_none_or(|known| distance < *known) {
+                    distances.insert(*id, distance);
+                    previous.insert(*id, current);
+                    to_explore.push(Reverse((distance, *id)));
                 }
-                explored.insert(current);
-                to_explore.extend(
-                    self.edges[current]
-                        .iter()
-                        .filter(|(id, _)| !explored.contains(id))
-                        .map(|(id, _)| (*id, distance + 1)),
-                );
-            } else {
-                return None;
             }
         }
+        distances.retain(|id, _| explored.contains(id));
+        (distances, previous)
     }
 
+    /// The number of edges on the shortest path between every pair of connected nodes.
     pub fn generate_cache(&self) -> HashMap<(Id, Id), usize> {
-        let mut map = HashMap::new();
-        for left in 0..self.nodes.len() {
-            for right in 0..self.nodes.len() {
-                if let Some(distance) = self.distance_between(left, right) {
-                    map.insert((left, right), distance);
+        self.live_ids()
+            .flat_map(|left| {
+                self.distances_from_with(left, |_, _, _| 1)
+                    .into_iter()
+                    .map(move |(right, distance)| ((left, right), distance as usize))
+            })
+            .collect()
+    }
+
+    /// Floyd–Warshall, O(nodes³)
+    ///
+    /// Return the cost of the cheapest path between every pair of nodes, the matrix is indexed by
+    /// [Id::index] and `matrix[from][to]` is `None` if there is no path. The cost of every edge
+    /// is given by `weight(from, to, edge)`, the paths whose cost overflows a `u64` are ignored.
+    pub fn floyd_warshall_with(
+        &self,
+        weight: impl Fn(Id, Id, &Edge) -> u64,
+    ) -> Vec<Vec<Option<u64>>> {
+        let len = self.nodes.len();
+        let mut matrix = vec![vec![None; len]; len];
+        for (from, edges) in self.edges.iter().enumerate() {
+            if self.nodes[from].is_some() {
+                matrix[from][from] = Some(0);
+            }
+            for (to, edge) in edges {
+                let cost = weight(self.id(from), *to, edge);
+                if matrix[from][to.index].is_none_or(|known| cost < known) {
+                    matrix[from][to.index] = Some(cost);
                 }
             }
         }
-        map
+
+        for middle in 0..len {
+            let through = matrix[middle].clone();
+            for row in &mut matrix {
+                let Some(first) = row[middle] else {
+                    continue;
+                };
+                for (known, second) in row.iter_mut().zip(&through) {
+                    let Some(cost) = second.and_then(|second| first.checked_add(second)) else {
+                        continue;
+                    };
+                    if known.is_none_or(|known| cost < known) {
+                        *known = Some(cost);
+                    }
+                }
+            }
+        }
+        matrix
+    }
+
+    /// Repeated BFS, O(nodes × (nodes + edges))
+    ///
+    /// Return the number of edges on the shortest path between every pair of nodes, the matrix is
+    /// indexed by [Id::index] and `matrix[from][to]` is `None` if there is no path.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_directed();
+    /// let a = graph.insert_value("a");
+    /// let b = graph.insert_value("b");
+    /// let c = graph.insert_value("c");
+    /// graph.create_edge(a, b);
+    /// graph.create_edge(b, c);
+    ///
+    /// let matrix = graph.all_pairs_bfs();
+    /// assert_eq!(matrix[a.index()], vec![Some(0), Some(1), Some(2)]);
+    /// assert_eq!(matrix[c.index()], vec![None, None, Some(0)]);
+    /// assert_eq!(matrix, graph.floyd_warshall_with(|_, _, _| 1));
+    /// ```
+    pub fn all_pairs_bfs(&self) -> Vec<Vec<Option<u64>>> {
+        let len = self.nodes.len();
+        let mut matrix = vec![vec![None; len]; len];
+        for (start, row) in matrix.iter_mut().enumerate() {
+            if self.nodes[start].is_none() {
+                continue;
+            }
+            row[start] = Some(0);
+            let mut to_explore = VecDeque::from([(start, 0)]);
+            while let Some((current, distance)) = to_explore.pop_front() {
+                for (id, _) in &self.edges[current] {
+                    if row[id.index].is_none() {
+                        row[id.index] = Some(distance + 1);
+                        to_explore.push_back((id.index, distance + 1));
+                    }
+                }
+            }
+        }
+        matrix
+    }
+
+    /// Build a graph containing only the nodes for which `keep` returns `true`, linked by
+    /// edges weighted by the number of edges on the shortest path between them in this graph,
+    /// see [Graph::contract_with].
+    pub fn contract(&self, keep: impl Fn(&Value) -> bool) -> Graph<Value, u64, Kind>
+    where
+        Kind: GraphKind,
+    {
+        self.contract_with(keep, |_, _, _| 1)
+    }
+
+    /// Build a graph containing only the nodes for which `keep` returns `true`. Every pair of
+    /// kept nodes connected in this graph is linked by an edge weighted by the cost of the
+    /// cheapest path between them, see [Graph::shortest_path_with].
+    /// The ids of the nodes aren't preserved.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_undirected();
+    /// let ids: Vec<_> = ["AA", "BB", "xx", "CC", "yy", "DD"]
+    ///     .into_iter()
+    ///     .map(|value| graph.insert_value(value))
+    ///     .collect();
+    /// for (from, to) in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 1)] {
+    ///     graph.create_edge(ids[from], ids[to]);
+    /// }
+    ///
+    /// let valves = graph.contract(|value| value.chars().all(|c| c.is_uppercase()));
+    /// let (aa, cc, dd) = (valves.get_id(&"AA").unwrap(), valves.get_id(&"CC").unwrap(), valves.get_id(&"DD").unwrap());
+    ///
+    /// assert_eq!(valves.values().count(), 4);
+    /// assert_eq!(valves.shortest_path(aa, cc).map(|(cost, _)| cost), Some(3));
+    /// assert!(valves.get_edges(aa).unwrap().iter().any(|&(id, cost)| id == cc && cost == 3));
+    /// assert_eq!(valves.get_edges(dd), Some(&[][..]));
+    /// ```
+    pub fn contract_with(
+        &self,
+        keep: impl Fn(&Value) -> bool,
+        weight: impl Fn(Id, Id, &Edge) -> u64,
+    ) -> Graph<Value, u64, Kind>
+    where
+        Kind: GraphKind,
+    {
+        let kept: Vec<Id> = self
+            .live_ids()
+            .filter(|id| self.nodes[id.index].as_ref().is_some_and(&keep))
+            .collect();
+
+        let mut graph = Graph::empty();
+        let new_ids: HashMap<Id, Id> = kept
+            .iter()
+            .map(|old| {
+                let value = self.nodes[old.index]
+                    .clone()
+                    .expect("Only existing nodes are kept");
+                (*old, graph.insert_value(value))
+            })
+            .collect();
+        for old in &kept {
+            let mut edges: Vec<(Id, u64)> = self
+                .distances_from_with(*old, &weight)
+                .into_iter()
+                .filter(|(to, _)| to != old)
+                .filter_map(|(to, distance)| Some((*new_ids.get(&to)?, distance)))
+                .collect();
+            edges.sort_unstable();
+            if Kind::DIRECTED {
+                for (to, _) in &edges {
+                    graph.predecessors[to.index].push(new_ids[old]);
+                }
+            }
+            graph.edges[new_ids[old].index] = edges;
+        }
+        graph
+    }
+
+    /// The longest simple path, computed from `order` if it is a topological order of the graph
+    /// and by exploring every path otherwise.
+    fn longest_path_with(
+        &self,
+        start: Id,
+        end: Id,
+        weight: impl Fn(Id, Id, &Edge) -> u64,
+        order: Option<Vec<Id>>,
+    ) -> Option<u64> {
+        let (start, end) = (self.index(start)?, self.index(end)?);
+
+        if let Some(order) = order {
+            let mut longest = vec![None; self.nodes.len()];
+            longest[start] = Some(0);
+            for from in order {
+                let Some(length) = longest[from.index] else {
+                    continue;
+                };
+                for (to, edge) in &self.edges[from.index] {
+                    longest[to.index] =
+                        longest[to.index].max(Some(length + weight(from, *to, edge)));
+                }
+            }
+            return longest[end];
+        }
+
+        // Number the live nodes from zero so the visited sets don't waste room on deleted slots.
+        let live: Vec<Id> = self.live_ids().collect();
+        let mut positions = vec![usize::MAX; self.nodes.len()];
+        for (position, id) in live.iter().enumerate() {
+            positions[id.index] = position;
+        }
+        let adjacency: Vec<Vec<(usize, u64)>> = live
+            .iter()
+            .map(|from| {
+                self.edges[from.index]
+                    .iter()
+                    .map(|(to, edge)| (positions[to.index], weight(*from, *to, edge)))
+                    .collect()
+            })
+            .collect();
+        let (start, end) = (positions[start], positions[end]);
+
+        fn explore(
+            adjacency: &[Vec<(usize, u64)>],
+            current: usize,
+            end: usize,
+            visited: &mut Visited,
+            length: u64,
+        ) -> Option<u64> {
+            if current == end {
+                return Some(length);
+            }
+            let mut longest = None;
+            for (next, cost) in &adjacency[current] {
+                if visited.insert(*next) {
+                    longest = longest.max(explore(adjacency, *next, end, visited, length + cost));
+                    visited.remove(*next);
+                }
+            }
+            longest
+        }
+
+        // Split the search in independent paths before running them in parallel.
+        let mut visited = Visited::new(live.len());
+        visited.insert(start);
+        let mut paths = vec![(start, visited, 0)];
+        let mut longest = None;
+        for _ in 0..8 {
+            if paths.len() >= 256 {
+                break;
+            }
+            let mut next_paths = Vec::new();
+            for (current, visited, length) in paths {
+                if current == end {
+                    longest = longest.max(Some(length));
+                    continue;
+                }
+                for (next, cost) in &adjacency[current] {
+                    let mut visited = visited.clone();
+                    if visited.insert(*next) {
+                        next_paths.push((*next, visited, length + cost));
+                    }
+                }
+            }
+            paths = next_paths;
+        }
+
+        paths
+            .into_par_iter()
+            .filter_map(|(current, mut visited, length)| {
+                explore(&adjacency, current, end, &mut visited, length)
+            })
+            .max()
+            .max(longest)
     }
 
     /// Iterate over all the node in the graph with no defined order
@@ -124,96 +785,1509 @@ where
     }
 }
 
+impl<Value, Edge, Kind> Graph<Value, Edge, Kind>
+where
+    Value: Clone + PartialEq + Eq + Hash + std::fmt::Debug,
+    Edge: Clone + Into<u64>,
+{
+    /// The cheapest path from `start` to `end` where the data of the edges are their cost,
+    /// see [Graph::shortest_path_with].
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_undirected();
+    /// let a = graph.insert_value("a");
+    /// let b = graph.insert_value("b");
+    /// let c = graph.insert_value("c");
+    /// graph.create_edge_with_data(a, b, 10_u32);
+    /// graph.create_edge_with_data(b, c, 1);
+    /// graph.create_edge_with_data(a, c, 3);
+    ///
+    /// assert_eq!(graph.shortest_path(a, b), Some((4, vec![a, c, b])));
+    /// ```
+    pub fn shortest_path(&self, start: Id, end: Id) -> Option<(u64, Vec<Id>)> {
+        self.shortest_path_with(start, end, |_, _, edge| edge.clone().into())
+    }
+
+    /// The cost of the cheapest path from `start` to every node reachable from it where the data
+    /// of the edges are their cost, see [Graph::shortest_path_with].
+    pub fn distances_from(&self, start: Id) -> HashMap<Id, u64> {
+        self.distances_from_with(start, |_, _, edge| edge.clone().into())
+    }
+
+    /// The cost of the cheapest path between every pair of nodes where the data of the edges are
+    /// their cost, see [Graph::floyd_warshall_with].
+    pub fn floyd_warshall(&self) -> Vec<Vec<Option<u64>>> {
+        self.floyd_warshall_with(|_, _, edge| edge.clone().into())
+    }
+}
+
+impl<Value, Edge> Graph<Value, Edge, Undirected>
+where
+    Value: Clone + PartialEq + Eq + Hash + std::fmt::Debug,
+{
+    /// Return the cost of the most expensive path from `start` to `end` never visiting the same
+    /// node twice, or `None` if `end` can't be reached. The cost of every edge is given by
+    /// `weight(from, to, edge)`.
+    ///
+    /// Every path is explored, which is exponential: the first levels are explored in parallel
+    /// and the visited nodes are stored in a bitset.
+    /// ```
+    /// use aoc::{Coord, Direction8, Graph, Grid};
+    ///
+    /// let grid = Grid::from(vec![vec!['.'; 4]; 4]);
+    /// let graph = Graph::from_grid(&grid, |_| true, &Direction8::ALL4);
+    /// let id = |x, y| graph.get_id(&Coord::at(x, y)).unwrap();
+    ///
+    /// assert_eq!(graph.longest_simple_path_with(id(0, 0), id(3, 0), |_, _, _| 1), Some(15));
+    /// assert_eq!(graph.longest_simple_path_with(id(0, 0), id(3, 3), |_, _, _| 1), Some(14));
+    /// ```
+    pub fn longest_simple_path_with(
+        &self,
+        start: Id,
+        end: Id,
+        weight: impl Fn(Id, Id, &Edge) -> u64,
+    ) -> Option<u64> {
+        self.longest_path_with(start, end, weight, None)
+    }
+}
+
+impl<Value, Edge> Graph<Value, Edge, Undirected>
+where
+    Value: Clone + PartialEq + Eq + Hash + std::fmt::Debug,
+    Edge: Clone + Into<u64>,
+{
+    /// The cost of the most expensive path from `start` to `end` never visiting the same node
+    /// twice where the data of the edges are their cost, see [Graph::longest_simple_path_with].
+    pub fn longest_simple_path(&self, start: Id, end: Id) -> Option<u64> {
+        self.longest_simple_path_with(start, end, |_, _, edge| edge.clone().into())
+    }
+}
+
 impl<Value, Edge> Graph<Value, Edge, Undirected>
 where
     Edge: Clone,
 {
     pub fn new_undirected() -> Self {
+        Self::empty()
+    }
+
+    /// # Panics
+    /// If one of the ids doesn't point to a node of the graph, see [Id].
+    pub fn create_edge_with_data(&mut self, a: Id, b: Id, metadata: Edge) {
+        let (left, right) = (self.expect_index(a), self.expect_index(b));
+        if self.edges[left].iter().all(|(id, _)| b != *id) {
+            self.edges[left].push((b, metadata.clone()));
+        }
+        if self.edges[right].iter().all(|(id, _)| a != *id) {
+            self.edges[right].push((a, metadata));
+        }
+    }
+
+    /// O(nodes + edges)
+    ///
+    /// The number of edges in the graph, every edge is counted once.
+    pub fn edge_count(&self) -> usize {
+        self.edges
+            .iter()
+            .enumerate()
+            .flat_map(|(from, edges)| edges.iter().filter(move |(to, _)| from <= to.index))
+            .count()
+    }
+}
+
+impl<Value, Edge> Graph<Value, Edge, Undirected>
+where
+    Value: Clone + PartialEq + Eq + Hash + std::fmt::Debug,
+{
+    /// O(nodes + edges)
+    ///
+    /// Group the nodes that are connected to each other. The components are sorted by their
+    /// smallest id and the ids of every component are sorted.
+    pub fn connected_components(&self) -> Vec<Vec<Id>> {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut components = Vec::new();
+        for root in self.live_ids() {
+            if seen[root.index] {
+                continue;
+            }
+            seen[root.index] = true;
+            let mut component = vec![root];
+            let mut to_explore = vec![root];
+            while let Some(current) = to_explore.pop() {
+                for (id, _) in &self.edges[current.index] {
+                    if !seen[id.index] {
+                        seen[id.index] = true;
+                        component.push(*id);
+                        to_explore.push(*id);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Tarjan, O(nodes + edges)
+    ///
+    /// Return the edges whose removal disconnects the graph, as sorted `(smallest id, biggest id)`
+    /// pairs.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_undirected();
+    /// let ids: Vec<_> = (0..6).map(|value| graph.insert_value(value)).collect();
+    /// for (a, b) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3)] {
+    ///     graph.create_edge(ids[a], ids[b]);
+    /// }
+    ///
+    /// assert_eq!(graph.bridges(), vec![(ids[2], ids[3])]);
+    /// assert_eq!(graph.articulation_points(), vec![ids[2], ids[3]]);
+    /// ```
+    pub fn bridges(&self) -> Vec<(Id, Id)> {
+        let mut bridges = Vec::new();
+        self.low_links(|parent, child, index, low, _| {
+            if low[child] > index[parent] {
+                bridges.push((self.id(parent.min(child)), self.id(parent.max(child))));
+            }
+        });
+        bridges.sort_unstable();
+        bridges
+    }
+
+    /// Tarjan, O(nodes + edges)
+    ///
+    /// Return the sorted nodes whose removal disconnects the graph, see [Graph::bridges].
+    pub fn articulation_points(&self) -> Vec<Id> {
+        let mut points = HashSet::new();
+        // A root is an articulation point if it has more than one child in the DFS tree.
+        let mut children = vec![0; self.nodes.len()];
+        self.low_links(|parent, child, index, low, is_root| {
+            if is_root {
+                children[parent] += 1;
+            } else if low[child] >= index[parent] {
+                points.insert(parent);
+            }
+        });
+        points.extend((0..self.nodes.len()).filter(|index| children[*index] > 1));
+        let mut points: Vec<_> = points.into_iter().map(|index| self.id(index)).collect();
+        points.sort_unstable();
+        points
+    }
+
+    /// Run an iterative DFS computing the discovery index and the low-link of every node.
+    /// Once a `child` is fully explored `on_child(parent, child, index, low, parent_is_root)` is
+    /// called, the nodes are given by their position.
+    fn low_links(&self, mut on_child: impl FnMut(usize, usize, &[usize], &[usize], bool)) {
+        let len = self.nodes.len();
+        let mut index = vec![usize::MAX; len];
+        let mut low = vec![usize::MAX; len];
+        let mut next_index = 0;
+
+        for root in self.live_ids().map(|id| id.index) {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            index[root] = next_index;
+            low[root] = next_index;
+            next_index += 1;
+            // The nodes being explored with their parent and the position of the next edge.
+            let mut call_stack = vec![(root, None, 0)];
+
+            while let Some((node, parent, position)) = call_stack.last_mut() {
+                let (node, parent) = (*node, *parent);
+                if let Some((next, _)) = self.edges[node].get(*position) {
+                    *position += 1;
+                    let next = next.index;
+                    if index[next] == usize::MAX {
+                        index[next] = next_index;
+                        low[next] = next_index;
+                        next_index += 1;
+                        call_stack.push((next, Some(node), 0));
+                    } else if Some(next) != parent {
+                        low[node] = low[node].min(index[next]);
+                    }
+                    continue;
+                }
+
+                call_stack.pop();
+                if let Some(parent) = parent {
+                    low[parent] = low[parent].min(low[node]);
+                    on_child(parent, node, &index, &low, parent == root);
+                }
+            }
+        }
+    }
+
+    /// Stoer–Wagner, O(nodes × edges × log nodes)
+    ///
+    /// Find the smallest set of edges whose removal splits the graph in two.
+    /// Returns `None` if the graph has less than two nodes.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_undirected();
+    /// let ids: Vec<_> = (0..8).map(|value| graph.insert_value(value)).collect();
+    /// for left in 0..4 {
+    ///     for right in left + 1..4 {
+    ///         graph.create_edge(ids[left], ids[right]);
+    ///         graph.create_edge(ids[left + 4], ids[right + 4]);
+    ///     }
+    /// }
+    /// graph.create_edge(ids[0], ids[4]);
+    /// graph.create_edge(ids[3], ids[7]);
+    ///
+    /// let cut = graph.min_cut().unwrap();
+    /// assert_eq!(cut.edges, vec![(ids[0], ids[4]), (ids[3], ids[7])]);
+    /// assert_eq!(cut.sizes, (4, 4));
+    /// ```
+    pub fn min_cut(&self) -> Option<MinCut> {
+        let ids: Vec<Id> = self.live_ids().collect();
+        let len = ids.len();
+        if len < 2 {
+            return None;
+        }
+        let index: HashMap<Id, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let mut weights: Vec<HashMap<usize, u64>> = ids
+            .iter()
+            .map(|id| {
+                self.edges[id.index]
+                    .iter()
+                    .map(|(other, _)| (index[other], 1))
+                    .collect()
+            })
+            .collect();
+        let mut members: Vec<Vec<Id>> = ids.iter().map(|id| vec![*id]).collect();
+        let mut active: Vec<usize> = (0..len).collect();
+        let mut best: Option<(u64, Vec<Id>)> = None;
+
+        while active.len() > 1 {
+            // Add the nodes one by one, always picking the most tightly connected to the others.
+            let mut connection = vec![0; len];
+            let mut added = vec![false; len];
+            let mut to_add: BinaryHeap<_> = active.iter().map(|node| (0, *node)).collect();
+            let (mut previous, mut last) = (None, None);
+            while let Some((weight, node)) = to_add.pop() {
+                if added[node] || weight != connection[node] {
+                    continue;
+                }
+                added[node] = true;
+                (previous, last) = (last, Some(node));
+                for (other, weight) in &weights[node] {
+                    if !added[*other] {
+                        connection[*other] += weight;
+                        to_add.push((connection[*other], *other));
+                    }
+                }
+            }
+            let (s, t) = (
+                previous.expect("Two active nodes"),
+                last.expect("Two active nodes"),
+            );
+            if best.as_ref().is_none_or(|(cut, _)| connection[t] < *cut) {
+                best = Some((connection[t], members[t].clone()));
+            }
+
+            // Merge `t` into `s`.
+            let merged = std::mem::take(&mut weights[t]);
+            for (other, weight) in merged {
+                weights[other].remove(&t);
+                if other != s {
+                    *weights[s].entry(other).or_default() += weight;
+                    *weights[other].entry(s).or_default() += weight;
+                }
+            }
+            let merged = std::mem::take(&mut members[t]);
+            members[s].extend(merged);
+            active.retain(|node| *node != t);
+        }
+
+        let (_, side) = best.expect("There was at least one phase");
+        let side: HashSet<Id> = side.into_iter().collect();
+        let mut cut: Vec<(Id, Id)> = side
+            .iter()
+            .flat_map(|id| {
+                self.edges[id.index]
+                    .iter()
+                    .map(move |(other, _)| (*id, *other))
+            })
+            .filter(|(_, other)| !side.contains(other))
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect();
+        cut.sort_unstable();
+        let (small, big) = (side.len(), len - side.len());
+        Some(MinCut {
+            edges: cut,
+            sizes: (small.min(big), small.max(big)),
+        })
+    }
+}
+
+impl<Value, Edge> Graph<Value, Edge, Undirected>
+where
+    Value: Clone + PartialEq + Eq + Hash + Ord + std::fmt::Debug,
+{
+    /// The neighbours of every node, indexed by position.
+    fn neighbours(&self) -> Vec<HashSet<Id>> {
+        self.edges
+            .iter()
+            .enumerate()
+            .map(|(index, edges)| {
+                edges
+                    .iter()
+                    .map(|(other, _)| *other)
+                    .filter(|other| other.index != index)
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn sorted_values(&self, ids: impl IntoIterator<Item = Id>) -> Vec<Value> {
+        let mut values: Vec<Value> = ids
+            .into_iter()
+            .map(|id| {
+                self.nodes[id.index]
+                    .clone()
+                    .expect("Only existing nodes are returned")
+            })
+            .collect();
+        values.sort();
+        values
+    }
+
+    /// Return all the sets of `k` nodes that are all connected to each other. The values of
+    /// every clique are sorted and the cliques are sorted.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_undirected();
+    /// for (a, b) in [("a", "b"), ("b", "c"), ("c", "a"), ("c", "d"), ("d", "b")] {
+    ///     let (a, b) = (graph.insert_value(a), graph.insert_value(b));
+    ///     graph.create_edge(a, b);
+    /// }
+    ///
+    /// assert_eq!(graph.triangles(), vec![vec!["a", "b", "c"], vec!["b", "c", "d"]]);
+    /// assert_eq!(graph.cliques_of_size(4), Vec::<Vec<&str>>::new());
+    /// assert_eq!(graph.maximum_clique().len(), 3);
+    /// assert_eq!(graph.maximum_independent_set().join(","), "a,d");
+    /// ```
+    pub fn cliques_of_size(&self, k: usize) -> Vec<Vec<Value>> {
+        fn extend(
+            neighbours: &[HashSet<Id>],
+            clique: &mut Vec<Id>,
+            candidates: Vec<Id>,
+            k: usize,
+            cliques: &mut Vec<Vec<Id>>,
+        ) {
+            if clique.len() == k {
+                cliques.push(clique.clone());
+                return;
+            }
+            for (i, id) in candidates.iter().enumerate() {
+                let next = candidates[i + 1..]
+                    .iter()
+                    .copied()
+                    .filter(|other| neighbours[id.index].contains(other))
+                    .collect();
+                clique.push(*id);
+                extend(neighbours, clique, next, k, cliques);
+                clique.pop();
+            }
+        }
+
+        let mut cliques = Vec::new();
+        extend(
+            &self.neighbours(),
+            &mut Vec::new(),
+            self.live_ids().collect(),
+            k,
+            &mut cliques,
+        );
+        let mut cliques: Vec<_> = cliques
+            .into_iter()
+            .map(|clique| self.sorted_values(clique))
+            .collect();
+        cliques.sort();
+        cliques
+    }
+
+    /// All the sets of three nodes connected to each other, see [Graph::cliques_of_size].
+    pub fn triangles(&self) -> Vec<Vec<Value>> {
+        self.cliques_of_size(3)
+    }
+
+    /// Bron–Kerbosch with pivoting
+    ///
+    /// Return the sorted values of the biggest set of nodes all connected to each other.
+    /// When multiple cliques have the maximum size any of them can be returned.
+    pub fn maximum_clique(&self) -> Vec<Value> {
+        let clique = Self::bron_kerbosch(&self.neighbours(), self.live_ids().collect());
+        self.sorted_values(clique)
+    }
+
+    /// Return the sorted values of the biggest set of nodes with no edge between them, this is
+    /// the maximum clique of the complement graph.
+    pub fn maximum_independent_set(&self) -> Vec<Value> {
+        let ids: HashSet<Id> = self.live_ids().collect();
+        let complement: Vec<HashSet<Id>> = self
+            .neighbours()
+            .into_iter()
+            .enumerate()
+            .map(|(index, neighbours)| {
+                ids.iter()
+                    .copied()
+                    .filter(|other| other.index != index && !neighbours.contains(other))
+                    .collect()
+            })
+            .collect();
+        self.sorted_values(Self::bron_kerbosch(&complement, ids))
+    }
+
+    fn bron_kerbosch(neighbours: &[HashSet<Id>], candidates: HashSet<Id>) -> Vec<Id> {
+        fn search(
+            neighbours: &[HashSet<Id>],
+            clique: &mut Vec<Id>,
+            mut candidates: HashSet<Id>,
+            mut excluded: HashSet<Id>,
+            best: &mut Vec<Id>,
+        ) {
+            if candidates.is_empty() && excluded.is_empty() {
+                if clique.len() > best.len() {
+                    *best = clique.clone();
+                }
+                return;
+            }
+            if clique.len() + candidates.len() <= best.len() {
+                return;
+            }
+            let pivot = candidates
+                .iter()
+                .chain(&excluded)
+                .max_by_key(|pivot| {
+                    candidates
+                        .iter()
+                        .filter(|id| neighbours[pivot.index].contains(id))
+                        .count()
+                })
+                .copied()
+                .expect("There is at least one candidate or excluded node");
+            let to_try: Vec<Id> = candidates
+                .iter()
+                .copied()
+                .filter(|id| !neighbours[pivot.index].contains(id))
+                .collect();
+
+            for id in to_try {
+                clique.push(id);
+                search(
+                    neighbours,
+                    clique,
+                    candidates
+                        .intersection(&neighbours[id.index])
+                        .copied()
+                        .collect(),
+                    excluded
+                        .intersection(&neighbours[id.index])
+                        .copied()
+                        .collect(),
+                    best,
+                );
+                clique.pop();
+                candidates.remove(&id);
+                excluded.insert(id);
+            }
+        }
+
+        let mut best = Vec::new();
+        search(
+            neighbours,
+            &mut Vec::new(),
+            candidates,
+            HashSet::new(),
+            &mut best,
+        );
+        best
+    }
+}
+
+/// The open neighbours of `coord` in the `grid`.
+fn open_neighbours<'a, T>(
+    grid: &'a Grid<T>,
+    coord: Coord<usize>,
+    is_open: &'a impl Fn(&T) -> bool,
+    connectivity: &'a [Direction8],
+) -> impl Iterator<Item = Coord<usize>> + 'a {
+    let coord = Coord::at(coord.x as isize, coord.y as isize);
+    connectivity.iter().filter_map(move |direction| {
+        let next = coord + *direction;
+        grid.get(next)
+            .is_some_and(is_open)
+            .then(|| Coord::at(next.x as usize, next.y as usize))
+    })
+}
+
+impl Graph<Coord<usize>, (), Undirected> {
+    /// Link every open cell of the `grid` to its open neighbours in the directions of
+    /// `connectivity`, usually [Direction8::ALL4] or [Direction8::ALL8].
+    /// ```
+    /// use aoc::{Coord, Direction8, Graph, Grid};
+    ///
+    /// let grid = Grid::from(vec![
+    ///     vec!['.', '.', '#'],
+    ///     vec!['#', '.', '.'],
+    /// ]);
+    ///
+    /// let graph = Graph::from_grid(&grid, |cell| *cell == '.', &Direction8::ALL4);
+    /// let (start, end) = (graph.get_id(&Coord::at(0, 0)).unwrap(), graph.get_id(&Coord::at(2, 1)).unwrap());
+    /// assert_eq!(graph.values().count(), 4);
+    /// assert_eq!(graph.distance_between(start, end), Some(3));
+    ///
+    /// let graph = Graph::from_grid(&grid, |cell| *cell == '.', &Direction8::ALL8);
+    /// let (start, end) = (graph.get_id(&Coord::at(0, 0)).unwrap(), graph.get_id(&Coord::at(2, 1)).unwrap());
+    /// assert_eq!(graph.distance_between(start, end), Some(2));
+    /// ```
+    pub fn from_grid<T>(
+        grid: &Grid<T>,
+        is_open: impl Fn(&T) -> bool,
+        connectivity: &[Direction8],
+    ) -> Self {
+        let mut graph = Self::new_undirected();
+        for (coord, cell) in grid.enumerate() {
+            if !is_open(cell) {
+                continue;
+            }
+            let id = graph.insert_value(coord);
+            for next in open_neighbours(grid, coord, &is_open, connectivity) {
+                let next = graph.insert_value(next);
+                graph.create_edge(id, next);
+            }
+        }
+        graph
+    }
+}
+
+impl Graph<Coord<usize>, u64, Undirected> {
+    /// Like [Graph::from_grid] but only the junctions are kept: the open cells that don't have
+    /// exactly two open neighbours. The junctions are linked by the corridors between them,
+    /// weighted by their length. When multiple corridors link the same junctions only the
+    /// shortest is kept. The corridors looping on a single junction and the loops without any
+    /// junction are dropped.
+    /// ```
+    /// use aoc::{Coord, Direction8, Graph, Grid};
+    ///
+    /// let grid: Vec<Vec<char>> = ["#.#####", "#...#.#", "#.#...#", "#...#.#", "#####.#"]
+    ///     .iter()
+    ///     .map(|line| line.chars().collect())
+    ///     .collect();
+    ///
+    /// let graph = Graph::from_grid_contracted(&Grid::from(grid), |cell| *cell != '#', &Direction8::ALL4);
+    /// let mut junctions: Vec<_> = graph.values().copied().collect();
+    /// junctions.sort();
+    /// assert_eq!(junctions.len(), 6);
+    /// assert!(junctions.contains(&Coord::at(5, 1)));
+    ///
+    /// let start = graph.get_id(&Coord::at(1, 0)).unwrap();
+    /// let end = graph.get_id(&Coord::at(5, 4)).unwrap();
+    /// assert_eq!(graph.shortest_path(start, end).map(|(cost, path)| (cost, path.len())), Some((8, 5)));
+    /// ```
+    pub fn from_grid_contracted<T>(
+        grid: &Grid<T>,
+        is_open: impl Fn(&T) -> bool,
+        connectivity: &[Direction8],
+    ) -> Self {
+        let is_junction =
+            |coord: Coord<usize>| open_neighbours(grid, coord, &is_open, connectivity).count() != 2;
+        let mut graph = Self::new_undirected();
+        for (coord, cell) in grid.enumerate() {
+            if !is_open(cell) || !is_junction(coord) {
+                continue;
+            }
+            let id = graph.insert_value(coord);
+            for first in open_neighbours(grid, coord, &is_open, connectivity) {
+                let (mut previous, mut current, mut length) = (coord, first, 1);
+                while !is_junction(current) {
+                    let next = open_neighbours(grid, current, &is_open, connectivity)
+                        .find(|next| *next != previous)
+                        .expect("A corridor has two open neighbours");
+                    (previous, current) = (current, next);
+                    length += 1;
+                    if current == coord {
+                        break;
+                    }
+                }
+                if current == coord {
+                    continue;
+                }
+                let other = graph.insert_value(current);
+                let known = graph.edges[id.index].iter().find(|(to, _)| *to == other);
+                if known.is_none_or(|(_, known)| length < *known) {
+                    graph.delete_edge(id, other);
+                    graph.create_edge_with_data(id, other, length);
+                }
+            }
+        }
+        graph
+    }
+}
+
+/// Parse one `a<separator>b` edge per line.
+fn parse_edge_lines<'a>(
+    input: &'a str,
+    separator: &'a str,
+) -> impl Iterator<Item = Result<(&'a str, &'a str)>> + 'a {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(move |line| {
+            let (from, to) = line
+                .split_once(separator)
+                .with_context(|| format!("Missing `{separator}` in `{line}`"))?;
+            Ok((from.trim(), to.trim()))
+        })
+}
+
+/// Parse one `a<separator>b, c, d` (or `a<separator>b c d`) adjacency list per line.
+fn parse_adjacency_lines<'a>(
+    input: &'a str,
+    separator: &'a str,
+) -> impl Iterator<Item = Result<(&'a str, Vec<&'a str>)>> + 'a {
+    parse_edge_lines(input, separator).map(|line| {
+        let (from, to) = line?;
+        let to = to
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|value| !value.is_empty())
+            .collect();
+        Ok((from, to))
+    })
+}
+
+impl Graph<String, (), Undirected> {
+    /// Parse one `a<separator>b` edge per line, for example `"a-b"`.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let graph = Graph::<String>::parse_edges("start-A\nstart-b\nA-c\nA-b", "-").unwrap();
+    /// let start = graph.get_id(&"start".to_string()).unwrap();
+    /// assert_eq!(graph.values().count(), 4);
+    /// assert_eq!(graph.get_edges(start).unwrap().len(), 2);
+    ///
+    /// assert!(Graph::<String>::parse_edges("a-b\nc", "-").is_err());
+    /// ```
+    pub fn parse_edges(input: &str, separator: &str) -> Result<Self> {
+        let mut graph = Self::new_undirected();
+        for line in parse_edge_lines(input, separator) {
+            let (from, to) = line?;
+            let (from, to) = (
+                graph.insert_value(from.to_string()),
+                graph.insert_value(to.to_string()),
+            );
+            graph.create_edge(from, to);
+        }
+        Ok(graph)
+    }
+
+    /// Parse one adjacency list per line, for example `"a: b c d"` or `"a <-> b, c"`.
+    pub fn parse_adjacency(input: &str, separator: &str) -> Result<Self> {
+        let mut graph = Self::new_undirected();
+        for line in parse_adjacency_lines(input, separator) {
+            let (from, to) = line?;
+            let from = graph.insert_value(from.to_string());
+            for to in to {
+                let to = graph.insert_value(to.to_string());
+                graph.create_edge(from, to);
+            }
+        }
+        Ok(graph)
+    }
+}
+
+impl Graph<String, (), Directed> {
+    /// Parse one `a<separator>b` edge going from `a` to `b` per line, for example `"a -> b"`.
+    pub fn parse_edges(input: &str, separator: &str) -> Result<Self> {
+        let mut graph = Self::new_directed();
+        for line in parse_edge_lines(input, separator) {
+            let (from, to) = line?;
+            let (from, to) = (
+                graph.insert_value(from.to_string()),
+                graph.insert_value(to.to_string()),
+            );
+            graph.create_edge(from, to);
+        }
+        Ok(graph)
+    }
+
+    /// Parse one adjacency list per line, for example `"a -> b, c"`.
+    /// ```
+    /// use aoc::{Directed, Graph};
+    ///
+    /// let graph = Graph::<String, (), Directed>::parse_adjacency("a -> b, c\nb -> c\nc ->", "->").unwrap();
+    /// let (a, c) = (graph.get_id(&"a".to_string()).unwrap(), graph.get_id(&"c".to_string()).unwrap());
+    /// assert_eq!(graph.get_edges(a).unwrap().len(), 2);
+    /// assert_eq!(graph.get_edges(c), Some(&[][..]));
+    /// ```
+    pub fn parse_adjacency(input: &str, separator: &str) -> Result<Self> {
+        let mut graph = Self::new_directed();
+        for line in parse_adjacency_lines(input, separator) {
+            let (from, to) = line?;
+            let from = graph.insert_value(from.to_string());
+            for to in to {
+                let to = graph.insert_value(to.to_string());
+                graph.create_edge(from, to);
+            }
+        }
+        Ok(graph)
+    }
+}
+
+impl Graph<String, usize, Directed> {
+    /// Parse one `AAA = (BBB, CCC)` node per line. Every node is linked to the nodes between the
+    /// parenthesis, the data of the edge is the position of the node in the parenthesis.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let graph = Graph::parse_branches("AAA = (BBB, CCC)\nBBB = (AAA, ZZZ)\nCCC = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+    /// let [aaa, ccc, zzz] = ["AAA", "CCC", "ZZZ"].map(|node| graph.get_id(&node.to_string()).unwrap());
+    ///
+    /// assert_eq!(graph.get_edges(aaa), Some(&[(graph.get_id(&"BBB".to_string()).unwrap(), 0), (ccc, 1)][..]));
+    /// assert_eq!(graph.get_edges(ccc), Some(&[(zzz, 0), (zzz, 1)][..]));
+    /// assert!(Graph::parse_branches("AAA = BBB, CCC").is_err());
+    /// ```
+    pub fn parse_branches(input: &str) -> Result<Self> {
+        let mut graph = Self::new_directed();
+        for line in parse_edge_lines(input, "=") {
+            let (from, to) = line?;
+            let to = to
+                .strip_prefix('(')
+                .and_then(|to| to.strip_suffix(')'))
+                .ok_or_else(|| anyhow!("Missing parenthesis around `{to}`"))?;
+            let from = graph.insert_value(from.to_string());
+            for (position, to) in to.split(',').enumerate() {
+                let to = graph.insert_value(to.trim().to_string());
+                graph.create_edge_with_data(from, to, position);
+            }
+        }
+        Ok(graph)
+    }
+}
+
+impl<Value> Graph<Value, (), Undirected> {
+    pub fn create_edge(&mut self, a: Id, b: Id) {
+        self.create_edge_with_data(a, b, ());
+    }
+}
+
+impl<Value, Edge> Graph<Value, Edge, Undirected>
+where
+    Value: Clone + PartialEq + Eq + Hash,
+    Edge: PartialEq + Eq,
+{
+    /// O(edge)
+    ///
+    /// Returns `None` if there is no such edge or an id is stale, see [Id].
+    pub fn delete_edge(&mut self, from: Id, to: Id) -> Option<Edge> {
+        let (left, right) = (self.index(from)?, self.index(to)?);
+
+        if let Some(position) = self.edges[left].iter().position(|(i, _)| *i == to) {
+            let left_edge = self.edges[left].remove(position).1;
+            let pos = self.edges[right]
+                .iter()
+                .position(|(i, _)| *i == from)
+                .expect("Corrupted graph");
+            let right_edge = self.edges[right].remove(pos).1;
+            debug_assert!(left_edge == right_edge, "left should be equal to right");
+            Some(left_edge)
+        } else {
+            None
+        }
+    }
+}
+
+impl<Value, Edge> Graph<Value, Edge, Directed> {
+    pub fn new_directed() -> Self {
+        Self::empty()
+    }
+
+    /// # Panics
+    /// If one of the ids doesn't point to a node of the graph, see [Id].
+    pub fn create_edge_with_data(&mut self, a: Id, b: Id, metadata: Edge) {
+        let (from, to) = (self.expect_index(a), self.expect_index(b));
+        self.edges[from].push((b, metadata));
+        self.predecessors[to].push(a);
+    }
+
+    /// O(1)
+    ///
+    /// The nodes with an edge to `id`, once per edge. Returns `None` if the node was deleted or
+    /// the id is stale, see [Id].
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_directed();
+    /// let a = graph.insert_value("a");
+    /// let b = graph.insert_value("b");
+    /// let c = graph.insert_value("c");
+    /// graph.create_edge(a, c);
+    /// graph.create_edge(b, c);
+    ///
+    /// assert_eq!(graph.predecessors(c), Some(&[a, b][..]));
+    /// assert_eq!(graph.predecessors(a), Some(&[][..]));
+    /// ```
+    pub fn predecessors(&self, id: Id) -> Option<&[Id]> {
+        Some(&self.predecessors[self.index(id)?])
+    }
+
+    /// O(nodes)
+    ///
+    /// The number of edges in the graph.
+    pub fn edge_count(&self) -> usize {
+        self.edges.iter().map(Vec::len).sum()
+    }
+}
+
+impl<Value, Edge> Graph<Value, Edge, Directed>
+where
+    Value: Clone + PartialEq + Eq + Hash,
+{
+    /// O(edge)
+    ///
+    /// Returns `None` if there is no such edge or an id is stale, see [Id].
+    pub fn delete_edge(&mut self, from: Id, to: Id) -> Option<Edge> {
+        let (index, to_index) = (self.index(from)?, self.index(to)?);
+
+        if let Some(position) = self.edges[index].iter().position(|(i, _)| *i == to) {
+            self.forget_predecessor(to_index, from);
+            Some(self.edges[index].remove(position).1)
+        } else {
+            None
+        }
+    }
+}
+
+impl<Value, Edge> Graph<Value, Edge, Directed>
+where
+    Value: Clone + PartialEq + Eq + Hash + std::fmt::Debug,
+{
+    fn in_degrees(&self) -> Vec<usize> {
+        let mut in_degrees = vec![0; self.nodes.len()];
+        for (id, _) in self.edges.iter().flatten() {
+            in_degrees[id.index] += 1;
+        }
+        in_degrees
+    }
+
+    /// Kahn, O((nodes + edges) log nodes)
+    ///
+    /// Order the nodes so every edge goes from a node to a node after it, the ties are broken by
+    /// picking the node with the smallest id first. Returns an error if the graph has a cycle.
+    /// See [Graph::topological_sort_by_key] to choose the order of the ties.
+    pub fn topological_sort(&self) -> Result<Vec<Id>> {
+        self.topological_sort_by_key(|id, _| id)
+    }
+
+    /// Order the nodes so every edge goes from a node to a node after it. When multiple nodes
+    /// are available the one with the smallest `key` comes first.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_directed();
+    /// for (before, after) in [('C', 'A'), ('C', 'F'), ('A', 'B'), ('A', 'D'), ('B', 'E'), ('D', 'E'), ('F', 'E')] {
+    ///     let (before, after) = (graph.insert_value(before), graph.insert_value(after));
+    ///     graph.create_edge(before, after);
+    /// }
+    ///
+    /// let order = graph.topological_sort_by_key(|_, step| *step).unwrap();
+    /// let order: String = order.into_iter().map(|id| graph.get_value(id).unwrap()).collect();
+    /// assert_eq!(order, "CABDFE");
+    ///
+    /// let (e, c) = (graph.get_id(&'E').unwrap(), graph.get_id(&'C').unwrap());
+    /// graph.create_edge(e, c);
+    /// assert!(graph.topological_sort().is_err());
+    /// ```
+    pub fn topological_sort_by_key<K: Ord>(
+        &self,
+        key: impl Fn(Id, &Value) -> K,
+    ) -> Result<Vec<Id>> {
+        let key = |id: Id| {
+            key(
+                id,
+                self.nodes[id.index]
+                    .as_ref()
+                    .expect("Only existing nodes are sorted"),
+            )
+        };
+        let mut in_degrees = self.in_degrees();
+        let mut available: BinaryHeap<_> = self
+            .live_ids()
+            .filter(|id| in_degrees[id.index] == 0)
+            .map(|id| Reverse((key(id), id)))
+            .collect();
+
+        let mut order = Vec::new();
+        while let Some(Reverse((_, current))) = available.pop() {
+            order.push(current);
+            for (id, _) in &self.edges[current.index] {
+                in_degrees[id.index] -= 1;
+                if in_degrees[id.index] == 0 {
+                    available.push(Reverse((key(*id), *id)));
+                }
+            }
+        }
+        ensure!(
+            order.len() == self.nodes_ids.len(),
+            "The graph contains a cycle, it can't be sorted"
+        );
+        Ok(order)
+    }
+
+    /// Kahn, O(nodes + edges)
+    ///
+    /// Unlike [Graph::topological_sort] the ties don't need to be ordered, so the nodes are
+    /// simply counted from a queue.
+    pub fn has_cycle(&self) -> bool {
+        let mut in_degrees = self.in_degrees();
+        let mut available: VecDeque<_> = self
+            .live_ids()
+            .map(|id| id.index)
+            .filter(|index| in_degrees[*index] == 0)
+            .collect();
+
+        let mut sorted = 0;
+        while let Some(current) = available.pop_front() {
+            sorted += 1;
+            for (id, _) in &self.edges[current] {
+                in_degrees[id.index] -= 1;
+                if in_degrees[id.index] == 0 {
+                    available.push_back(id.index);
+                }
+            }
+        }
+        sorted != self.nodes_ids.len()
+    }
+
+    /// Return the cost of the most expensive path from `start` to `end` never visiting the same
+    /// node twice, or `None` if `end` can't be reached. The cost of every edge is given by
+    /// `weight(from, to, edge)`.
+    ///
+    /// If the graph is acyclic the path is found in O(nodes + edges) from the topological
+    /// order. Otherwise every path is explored, like in an undirected graph.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_directed();
+    /// let ids: Vec<_> = (0..4).map(|value| graph.insert_value(value)).collect();
+    /// for (from, to) in [(0, 1), (1, 3), (0, 2), (2, 1)] {
+    ///     graph.create_edge(ids[from], ids[to]);
+    /// }
+    ///
+    /// assert_eq!(graph.longest_simple_path_with(ids[0], ids[3], |_, _, _| 1), Some(3));
+    /// graph.create_edge(ids[3], ids[2]);
+    /// assert_eq!(graph.longest_simple_path_with(ids[0], ids[3], |_, _, _| 1), Some(3));
+    /// assert_eq!(graph.longest_simple_path_with(ids[3], ids[0], |_, _, _| 1), None);
+    /// ```
+    pub fn longest_simple_path_with(
+        &self,
+        start: Id,
+        end: Id,
+        weight: impl Fn(Id, Id, &Edge) -> u64,
+    ) -> Option<u64> {
+        self.longest_path_with(start, end, weight, self.topological_sort().ok())
+    }
+
+    /// Tarjan, O(nodes + edges)
+    ///
+    /// Group the nodes that can all reach each other. The components are returned in reverse
+    /// topological order: no edge goes from a component to a component before it.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_directed();
+    /// let ids: Vec<_> = (0..5).map(|value| graph.insert_value(value)).collect();
+    /// for (from, to) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3)] {
+    ///     graph.create_edge(ids[from], ids[to]);
+    /// }
+    ///
+    /// let mut components = graph.strongly_connected_components();
+    /// components.iter_mut().for_each(|component| component.sort());
+    /// assert_eq!(components, vec![vec![ids[3], ids[4]], vec![ids[0], ids[1], ids[2]]]);
+    /// ```
+    pub fn strongly_connected_components(&self) -> Vec<Vec<Id>> {
+        let len = self.nodes.len();
+        let mut index: Vec<Option<usize>> = vec![None; len];
+        let mut low = vec![0; len];
+        let mut on_stack = vec![false; len];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut next_index = 0;
+
+        for root in self.live_ids().map(|id| id.index) {
+            if index[root].is_some() {
+                continue;
+            }
+            // The nodes being explored with the position of the next edge to follow.
+            let mut call_stack = vec![(root, 0)];
+            index[root] = Some(next_index);
+            low[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some((node, position)) = call_stack.last_mut() {
+                let node = *node;
+                if let Some((next, _)) = self.edges[node].get(*position) {
+                    *position += 1;
+                    let next = next.index;
+                    match index[next] {
+                        None => {
+                            index[next] = Some(next_index);
+                            low[next] = next_index;
+                            next_index += 1;
+                            stack.push(next);
+                            on_stack[next] = true;
+                            call_stack.push((next, 0));
+                        }
+                        Some(next_index) if on_stack[next] => {
+                            low[node] = low[node].min(next_index);
+                        }
+                        Some(_) => (),
+                    }
+                    continue;
+                }
+
+                call_stack.pop();
+                if let Some((parent, _)) = call_stack.last() {
+                    low[*parent] = low[*parent].min(low[node]);
+                }
+                if Some(low[node]) == index[node] {
+                    let mut component = Vec::new();
+                    loop {
+                        let id = stack.pop().expect("The node is on the stack");
+                        on_stack[id] = false;
+                        component.push(self.id(id));
+                        if id == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    /// Merge every strongly connected component into a single node containing all its values,
+    /// the resulting graph is acyclic. Also returns the id of the component of every node.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_directed();
+    /// let ids: Vec<_> = (0..4).map(|value| graph.insert_value(value)).collect();
+    /// for (from, to) in [(0, 1), (1, 0), (1, 2), (0, 2), (2, 3)] {
+    ///     graph.create_edge(ids[from], ids[to]);
+    /// }
+    ///
+    /// let (dag, components) = graph.condensation();
+    /// assert!(!dag.has_cycle());
+    /// assert_eq!(dag.values().count(), 3);
+    /// assert_eq!(components[&ids[0]], components[&ids[1]]);
+    /// assert_eq!(dag.get_edges(components[&ids[0]]), Some(&[(components[&ids[2]], ())][..]));
+    /// ```
+    pub fn condensation(&self) -> (Graph<Vec<Value>, (), Directed>, HashMap<Id, Id>) {
+        let mut dag = Graph::new_directed();
+        let mut components = HashMap::new();
+        for component in self.strongly_connected_components().into_iter().rev() {
+            let values = component
+                .iter()
+                .map(|id| {
+                    self.nodes[id.index]
+                        .clone()
+                        .expect("Only existing nodes are in components")
+                })
+                .collect();
+            let component_id = dag.insert_value(values);
+            components.extend(component.into_iter().map(|id| (id, component_id)));
+        }
+        let mut linked = HashSet::new();
+        for (from, edges) in self.edges.iter().enumerate() {
+            for (to, _) in edges {
+                let (from, to) = (components[&self.id(from)], components[to]);
+                if from != to && linked.insert((from, to)) {
+                    dag.create_edge(from, to);
+                }
+            }
+        }
+        (dag, components)
+    }
+
+    /// Simulate `workers` working on the nodes in topological order, every node taking
+    /// `duration` to complete and a node can only start once all the nodes leading to it are
+    /// complete. When multiple nodes are available the one with the smallest `key` is started
+    /// first.
+    ///
+    /// Returns the time at which all the nodes are complete and the order in which they were
+    /// started. Returns an error if there is no worker or if the graph has a cycle.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let mut graph = Graph::new_directed();
+    /// for (before, after) in [('C', 'A'), ('C', 'F'), ('A', 'B'), ('A', 'D'), ('B', 'E'), ('D', 'E'), ('F', 'E')] {
+    ///     let (before, after) = (graph.insert_value(before), graph.insert_value(after));
+    ///     graph.create_edge(before, after);
+    /// }
+    ///
+    /// let duration = |_, step: &char| (*step as u8 - b'A' + 1) as u64;
+    /// let (time, order) = graph.schedule(2, duration, |_, step| *step).unwrap();
+    /// let order: String = order.into_iter().map(|id| graph.get_value(id).unwrap()).collect();
+    ///
+    /// assert_eq!(time, 15);
+    /// assert_eq!(order, "CAFBDE");
+    /// ```
+    pub fn schedule<K: Ord>(
+        &self,
+        workers: usize,
+        duration: impl Fn(Id, &Value) -> u64,
+        key: impl Fn(Id, &Value) -> K,
+    ) -> Result<(u64, Vec<Id>)> {
+        ensure!(workers > 0, "Nothing can be done without any worker");
+        let value = |id: Id| {
+            self.nodes[id.index]
+                .as_ref()
+                .expect("Only existing nodes are scheduled")
+        };
+        let mut in_degrees = self.in_degrees();
+        let mut available: BinaryHeap<_> = self
+            .live_ids()
+            .filter(|id| in_degrees[id.index] == 0)
+            .map(|id| Reverse((key(id, value(id)), id)))
+            .collect();
+        let mut running = BinaryHeap::new();
+        let mut order = Vec::new();
+        let mut time = 0;
+
+        loop {
+            while running.len() < workers {
+                let Some(Reverse((_, id))) = available.pop() else {
+                    break;
+                };
+                order.push(id);
+                running.push(Reverse((time + duration(id, value(id)), id)));
+            }
+            let Some(Reverse((end, _))) = running.peek() else {
+                break;
+            };
+            time = *end;
+            while running.peek().is_some_and(|Reverse((end, _))| *end == time) {
+                let Reverse((_, id)) = running.pop().expect("We just peeked it");
+                for (next, _) in &self.edges[id.index] {
+                    in_degrees[next.index] -= 1;
+                    if in_degrees[next.index] == 0 {
+                        available.push(Reverse((key(*next, value(*next)), *next)));
+                    }
+                }
+            }
+        }
+        ensure!(
+            order.len() == self.nodes_ids.len(),
+            "The graph contains a cycle, it can't be scheduled"
+        );
+        Ok((time, order))
+    }
+}
+
+impl<Value, Edge> Graph<Value, Edge, Directed>
+where
+    Value: Clone + PartialEq + Eq + Hash + std::fmt::Debug,
+    Edge: Clone + Into<u64>,
+{
+    /// The cost of the most expensive path from `start` to `end` never visiting the same node
+    /// twice where the data of the edges are their cost, see [Graph::longest_simple_path_with].
+    pub fn longest_simple_path(&self, start: Id, end: Id) -> Option<u64> {
+        self.longest_simple_path_with(start, end, |_, _, edge| edge.clone().into())
+    }
+}
+
+impl<Value> Graph<Value, (), Directed> {
+    pub fn create_edge(&mut self, a: Id, b: Id) {
+        self.create_edge_with_data(a, b, ());
+    }
+}
+
+/// How to draw a node, see [Export::with_node_style].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeStyle {
+    /// Any color known by Graphviz and Mermaid, like `red` or `#ff8800`.
+    pub color: Option<String>,
+    /// A Graphviz shape like `box`, `circle` or `diamond`, Mermaid uses the closest one.
+    pub shape: Option<String>,
+}
+
+type NodeStyler<'a, Value> = Box<dyn Fn(Id, &Value) -> NodeStyle + 'a>;
+type EdgeLabeler<'a, Edge> = Box<dyn Fn(&Edge) -> String + 'a>;
+
+/// The color of the nodes and edges given to [Export::with_path].
+const HIGHLIGHT: &str = "red";
+
+/// A configurable export of a [Graph] to the DOT language of Graphviz or to a Mermaid
+/// flowchart, see [Graph::export].
+pub struct Export<'a, Value, Edge, Kind> {
+    graph: &'a Graph<Value, Edge, Kind>,
+    edge_label: Option<EdgeLabeler<'a, Edge>>,
+    node_style: Option<NodeStyler<'a, Value>>,
+    path_nodes: HashSet<Id>,
+    path_edges: HashSet<(Id, Id)>,
+}
+
+impl<Value, Edge, Kind> Graph<Value, Edge, Kind> {
+    /// Export the graph, the nodes are named by their [Id::index] and labelled by their value.
+    /// ```
+    /// use aoc::{Graph, NodeStyle};
+    ///
+    /// let mut graph = Graph::new_directed();
+    /// let a = graph.insert_value("a");
+    /// let b = graph.insert_value("b");
+    /// let c = graph.insert_value("c");
+    /// graph.create_edge_with_data(a, b, 3);
+    /// graph.create_edge_with_data(b, c, 4);
+    ///
+    /// let export = graph
+    ///     .export()
+    ///     .with_edge_labels()
+    ///     .with_node_style(|_, value| NodeStyle {
+    ///         shape: (*value == "c").then(|| "box".to_string()),
+    ///         ..NodeStyle::default()
+    ///     })
+    ///     .with_path(&[a, b]);
+    /// assert_eq!(
+    ///     export.to_dot().lines().map(str::trim).collect::<Vec<_>>(),
+    ///     [
+    ///         r#"digraph a {"#,
+    ///         r#""0" [label = "a", color = "red", penwidth = "2"]"#,
+    ///         r#""1" [label = "b", color = "red", penwidth = "2"]"#,
+    ///         r#""2" [label = "c", shape = "box"]"#,
+    ///         r#""0" -> "1" [label = "3", color = "red", penwidth = "2"]"#,
+    ///         r#""1" -> "2" [label = "4"]"#,
+    ///         r#"}"#,
+    ///     ]
+    /// );
+    /// assert_eq!(
+    ///     export.to_mermaid(),
+    ///     r#"flowchart LR
+    ///     n0["a"]
+    ///     n1["b"]
+    ///     n2["c"]
+    ///     n0 -->|"3"| n1
+    ///     n1 -->|"4"| n2
+    ///     style n0 stroke:red,stroke-width:2px
+    ///     style n1 stroke:red,stroke-width:2px
+    ///     linkStyle 0 stroke:red,stroke-width:2px
+    /// "#
+    /// );
+    /// ```
+    pub fn export(&self) -> Export<'_, Value, Edge, Kind> {
+        Export {
+            graph: self,
+            edge_label: None,
+            node_style: None,
+            path_nodes: HashSet::new(),
+            path_edges: HashSet::new(),
+        }
+    }
+}
+
+impl<'a, Value, Edge, Kind> Export<'a, Value, Edge, Kind> {
+    /// Label every edge with its data.
+    pub fn with_edge_labels(self) -> Self
+    where
+        Edge: std::fmt::Display,
+    {
         Self {
-            nodes_ids: HashMap::new(),
-            nodes: Vec::new(),
-            edges: Vec::new(),
-            kind: PhantomData,
+            edge_label: Some(Box::new(|edge| edge.to_string())),
+            ..self
         }
     }
 
-    pub fn create_edge_with_data(&mut self, a: Id, b: Id, metadata: Edge) {
-        if self.edges[a].iter().all(|(id, _)| b != *id) {
-            self.edges[a].push((b, metadata.clone()));
+    /// Style every node with `style(id, value)`.
+    pub fn with_node_style(self, style: impl Fn(Id, &Value) -> NodeStyle + 'a) -> Self {
+        Self {
+            node_style: Some(Box::new(style)),
+            ..self
         }
-        if self.edges[b].iter().all(|(id, _)| a != *id) {
-            self.edges[b].push((a, metadata));
+    }
+
+    /// Highlight the nodes of `path` and the edges between consecutive nodes, their color
+    /// overrides the one given by [Export::with_node_style].
+    pub fn with_path(self, path: &[Id]) -> Self {
+        Self {
+            path_nodes: path.iter().copied().collect(),
+            path_edges: path.windows(2).map(|pair| (pair[0], pair[1])).collect(),
+            ..self
         }
     }
-}
 
-impl<Value> Graph<Value, (), Undirected> {
-    pub fn create_edge(&mut self, a: Id, b: Id) {
-        self.create_edge_with_data(a, b, ());
+    /// The nodes of the graph with their style.
+    fn nodes(&self) -> impl Iterator<Item = (Id, &'a Value, NodeStyle)> + '_ {
+        let graph = self.graph;
+        graph.live_ids().map(move |id| {
+            let value = graph.nodes[id.index]
+                .as_ref()
+                .expect("Only existing nodes are exported");
+            let mut style = self
+                .node_style
+                .as_ref()
+                .map(|style| style(id, value))
+                .unwrap_or_default();
+            if self.path_nodes.contains(&id) {
+                style.color = Some(HIGHLIGHT.to_string());
+            }
+            (id, value, style)
+        })
     }
-}
 
-impl<Value, Edge> Graph<Value, Edge, Undirected>
-where
-    Value: Clone + PartialEq + Eq + Hash,
-    Edge: PartialEq + Eq,
-{
-    /// O(edge)
-    pub fn delete_edge(&mut self, from: Id, to: Id) -> Option<Edge> {
-        debug_assert!(from < self.edges.len());
-        debug_assert!(to < self.edges.len());
+    /// The edges of the graph with their label and whether they are on the highlighted path,
+    /// the edges of an undirected graph are given once.
+    fn edges(&self, directed: bool) -> impl Iterator<Item = (Id, Id, Option<String>, bool)> + '_ {
+        let graph = self.graph;
+        graph
+            .edges
+            .iter()
+            .enumerate()
+            .flat_map(move |(from, edges)| {
+                let from = graph.id(from);
+                edges
+                    .iter()
+                    .filter(move |(to, _)| directed || from.index <= to.index)
+                    .map(move |(to, edge)| {
+                        let label = self.edge_label.as_ref().map(|label| label(edge));
+                        let highlighted = self.path_edges.contains(&(from, *to))
+                            || (!directed && self.path_edges.contains(&(*to, from)));
+                        (from, *to, label, highlighted)
+                    })
+            })
+    }
 
-        if let Some(position) = self.edges[from].iter().position(|(i, _)| *i == to) {
-            let left = self.edges[from].remove(position).1;
-            let pos = self.edges[to]
-                .iter()
-                .position(|(i, _)| *i == from)
-                .expect("Corrupted graph");
-            let right = self.edges[to].remove(pos).1;
-            debug_assert!(left == right, "left should be equal to right");
-            Some(left)
+    fn to_dot_with(&self, directed: bool) -> String
+    where
+        Value: std::fmt::Display,
+    {
+        let (header, arrow) = if directed {
+            ("digraph", "->")
         } else {
-            None
+            ("graph", "--")
+        };
+        let mut dot = format!("{header} a {{\n");
+        for (id, value, style) in self.nodes() {
+            let mut attributes = vec![("label", value.to_string())];
+            attributes.extend(style.color.map(|color| ("color", color)));
+            attributes.extend(style.shape.map(|shape| ("shape", shape)));
+            if self.path_nodes.contains(&id) {
+                attributes.push(("penwidth", "2".to_string()));
+            }
+            dot += &format!("\t \"{}\"{}\n", id.index, dot_attributes(&attributes));
         }
+        for (from, to, label, highlighted) in self.edges(directed) {
+            let mut attributes: Vec<_> = label.map(|label| ("label", label)).into_iter().collect();
+            if highlighted {
+                attributes.push(("color", HIGHLIGHT.to_string()));
+                attributes.push(("penwidth", "2".to_string()));
+            }
+            dot += &format!(
+                "\t \"{}\" {arrow} \"{}\"{}\n",
+                from.index,
+                to.index,
+                dot_attributes(&attributes)
+            );
+        }
+        dot + "}\n"
     }
-}
 
-impl<Value, Edge> Graph<Value, Edge, Directed> {
-    pub fn new_directed() -> Self {
-        Self {
-            nodes_ids: HashMap::new(),
-            nodes: Vec::new(),
-            edges: Vec::new(),
-            kind: PhantomData,
+    fn to_mermaid_with(&self, directed: bool) -> String
+    where
+        Value: std::fmt::Display,
+    {
+        let arrow = if directed { "-->" } else { "---" };
+        let mut mermaid = String::from("flowchart LR\n");
+        let mut styles = String::new();
+        for (id, value, style) in self.nodes() {
+            let (open, close) = match style.shape.as_deref() {
+                Some("ellipse" | "oval") => ("(", ")"),
+                Some("circle" | "doublecircle") => ("((", "))"),
+                Some("diamond") => ("{", "}"),
+                Some("hexagon") => ("{{", "}}"),
+                _ => ("[", "]"),
+            };
+            let label = mermaid_escape(&value.to_string());
+            mermaid += &format!("    n{}{open}\"{label}\"{close}\n", id.index);
+
+            let mut properties = Vec::new();
+            properties.extend(style.color.map(|color| format!("stroke:{color}")));
+            if self.path_nodes.contains(&id) {
+                properties.push("stroke-width:2px".to_string());
+            }
+            if !properties.is_empty() {
+                styles += &format!("    style n{} {}\n", id.index, properties.join(","));
+            }
+        }
+        for (position, (from, to, label, highlighted)) in self.edges(directed).enumerate() {
+            let label = label
+                .map(|label| format!("|\"{}\"|", mermaid_escape(&label)))
+                .unwrap_or_default();
+            mermaid += &format!("    n{} {arrow}{label} n{}\n", from.index, to.index);
+            if highlighted {
+                styles +=
+                    &format!("    linkStyle {position} stroke:{HIGHLIGHT},stroke-width:2px\n");
+            }
         }
+        mermaid + &styles
     }
+}
 
-    pub fn create_edge_with_data(&mut self, a: Id, b: Id, metadata: Edge) {
-        self.edges[a].push((b, metadata));
+impl<Value, Edge> Export<'_, Value, Edge, Directed>
+where
+    Value: std::fmt::Display,
+{
+    /// Render a DOT document, see [Graph::parse_dot] to read it back.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with(true)
+    }
+
+    /// Render a Mermaid flowchart.
+    pub fn to_mermaid(&self) -> String {
+        self.to_mermaid_with(true)
     }
 }
 
-impl<Value, Edge> Graph<Value, Edge, Directed>
+impl<Value, Edge> Export<'_, Value, Edge, Undirected>
 where
-    Value: Clone + PartialEq + Eq + Hash,
+    Value: std::fmt::Display,
 {
-    /// O(edge)
-    pub fn delete_edge(&mut self, from: Id, to: Id) -> Option<Edge> {
-        debug_assert!(from < self.edges.len());
-        debug_assert!(to < self.edges.len());
+    /// Render a DOT document, see [Graph::parse_dot] to read it back.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with(false)
+    }
 
-        if let Some(position) = self.edges[from].iter().position(|(i, _)| *i == to) {
-            Some(self.edges[from].remove(position).1)
-        } else {
-            None
-        }
+    /// Render a Mermaid flowchart.
+    pub fn to_mermaid(&self) -> String {
+        self.to_mermaid_with(false)
     }
 }
 
-impl<Value> Graph<Value, (), Directed> {
-    pub fn create_edge(&mut self, a: Id, b: Id) {
-        self.create_edge_with_data(a, b, ());
+/// Format ` [key = "value", …]`, or nothing without attributes.
+fn dot_attributes(attributes: &[(&str, String)]) -> String {
+    if attributes.is_empty() {
+        return String::new();
     }
+    let attributes: Vec<_> = attributes
+        .iter()
+        .map(|(key, value)| {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            format!("{key} = \"{value}\"")
+        })
+        .collect();
+    format!(" [{}]", attributes.join(", "))
+}
+
+fn mermaid_escape(text: &str) -> String {
+    text.replace('"', "#quot;")
 }
 
 impl<Value, Edge> std::fmt::Display for Graph<Value, Edge, Directed>
@@ -221,18 +2295,7 @@ where
     Value: std::fmt::Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "digraph a {{")?;
-        for (idx, node) in self.nodes.iter().enumerate() {
-            if let Some(node) = node {
-                writeln!(f, "\t \"{}\" [label = \"{}\"]", idx, node)?;
-            }
-        }
-        for (idx, edges) in self.edges.iter().enumerate() {
-            for edge in edges {
-                writeln!(f, "\t \"{}\" -> \"{}\"", idx, edge.0)?;
-            }
-        }
-        writeln!(f, "}}")
+        f.write_str(&self.export().to_dot())
     }
 }
 
@@ -241,25 +2304,253 @@ where
     Value: std::fmt::Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "graph a {{")?;
-        for (idx, node) in self.nodes.iter().enumerate() {
-            if let Some(node) = node {
-                writeln!(f, "\t \"{}\" [label = \"{}\"]", idx, node)?;
+        f.write_str(&self.export().to_dot())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DotToken {
+    /// An identifier, a number or a quoted string.
+    Word(String),
+    Symbol(&'static str),
+}
+
+fn dot_tokens(input: &str) -> Result<Vec<DotToken>> {
+    const SYMBOLS: [&str; 9] = ["->", "--", "{", "}", "[", "]", "=", ";", ","];
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '.';
+
+    let mut tokens = Vec::new();
+    let mut rest = input;
+    let mut at_line_start = true;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            at_line_start |= c == '\n';
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        // `#` only starts a comment at the beginning of a line, like the C preprocessor output.
+        let line_comment = rest.starts_with("//") || (at_line_start && rest.starts_with('#'));
+        at_line_start = line_comment;
+        if line_comment {
+            rest = rest.split_once('\n').map_or("", |(_, rest)| rest);
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            let (_, after) = comment
+                .split_once("*/")
+                .context("Unterminated `/*` comment")?;
+            rest = after;
+        } else if let Some(symbol) = SYMBOLS.into_iter().find(|symbol| rest.starts_with(symbol)) {
+            tokens.push(DotToken::Symbol(symbol));
+            rest = &rest[symbol.len()..];
+        } else if let Some(quoted) = rest.strip_prefix('"') {
+            let mut word = String::new();
+            let mut chars = quoted.char_indices();
+            let end = loop {
+                match chars.next() {
+                    Some((end, '"')) => break end,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, c @ ('"' | '\\'))) => word.push(c),
+                        Some((_, c)) => word.extend(['\\', c]),
+                        None => {}
+                    },
+                    Some((_, c)) => word.push(c),
+                    None => return Err(anyhow!("Unterminated string `{rest}`")),
+                }
+            };
+            tokens.push(DotToken::Word(word));
+            rest = &quoted[end + 1..];
+        } else if is_word(c) || c == '-' {
+            let end = rest[1..]
+                .find(|c| !is_word(c))
+                .map_or(rest.len(), |end| end + 1);
+            tokens.push(DotToken::Word(rest[..end].to_string()));
+            rest = &rest[end..];
+        } else {
+            return Err(anyhow!("Unexpected `{c}` in `{rest}`"));
+        }
+    }
+    Ok(tokens)
+}
+
+/// The nodes in order of appearance with their label and the edges with their label, as
+/// described by a DOT document.
+type DotStatements = (Vec<(String, Option<String>)>, Vec<(String, String, String)>);
+
+/// Parse a DOT document made of node and edge statements, the attributes other than `label`
+/// and the graph, node and edge defaults are ignored.
+fn parse_dot_statements(input: &str, directed: bool) -> Result<DotStatements> {
+    use DotToken::{Symbol, Word};
+    let (keyword, arrow) = if directed {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
+
+    let tokens = dot_tokens(input)?;
+    let mut tokens = tokens.into_iter().peekable();
+    tokens.next_if(|token| matches!(token, Word(word) if word == "strict"));
+    match tokens.next() {
+        Some(Word(word)) if word == keyword => (),
+        token => return Err(anyhow!("Expected `{keyword}`, found {token:?}")),
+    }
+    tokens.next_if(|token| matches!(token, Word(_)));
+    ensure!(
+        tokens.next() == Some(Symbol("{")),
+        "Expected `{{` after `{keyword}`"
+    );
+
+    let mut nodes: Vec<(String, Option<String>)> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut edges = Vec::new();
+    let mut declare = |name: &str, label: Option<String>| {
+        let position = *positions.entry(name.to_string()).or_insert_with(|| {
+            nodes.push((name.to_string(), None));
+            nodes.len() - 1
+        });
+        if label.is_some() {
+            nodes[position].1 = label;
+        }
+    };
+
+    loop {
+        let name = match tokens.next() {
+            Some(Symbol("}")) => break,
+            Some(Symbol(";" | ",")) => continue,
+            Some(Word(name)) => name,
+            Some(Symbol(symbol)) => return Err(anyhow!("Unexpected `{symbol}`")),
+            None => return Err(anyhow!("Missing the closing `}}`")),
+        };
+        if tokens.next_if_eq(&Symbol("=")).is_some() {
+            // A graph attribute like `rankdir = LR`.
+            ensure!(
+                matches!(tokens.next(), Some(Word(_))),
+                "Missing the value of `{name}`"
+            );
+            continue;
+        }
+
+        let mut chain = vec![name];
+        while let Some(Symbol(symbol)) =
+            tokens.next_if(|token| matches!(token, Symbol("->" | "--")))
+        {
+            ensure!(symbol == arrow, "Unexpected `{symbol}` in a `{keyword}`");
+            match tokens.next() {
+                Some(Word(name)) => chain.push(name),
+                token => return Err(anyhow!("Expected a node after `{arrow}`, found {token:?}")),
             }
         }
-        let mut already_inserted = HashSet::new();
 
-        for (left, edges) in self.edges.iter().enumerate() {
-            for (right, _) in edges {
-                if !already_inserted.contains(&(left, *right))
-                    && !already_inserted.contains(&(*right, left))
-                {
-                    already_inserted.insert((left, *right));
-                    writeln!(f, "\t \"{}\" -- \"{}\"", left, right)?;
+        let mut label = None;
+        if tokens.next_if_eq(&Symbol("[")).is_some() {
+            loop {
+                match tokens.next() {
+                    Some(Symbol("]")) => break,
+                    Some(Symbol(";" | ",")) => (),
+                    Some(Word(key)) => {
+                        ensure!(
+                            tokens.next() == Some(Symbol("=")),
+                            "Missing `=` after `{key}`"
+                        );
+                        match tokens.next() {
+                            Some(Word(value)) if key == "label" => label = Some(value),
+                            Some(Word(_)) => (),
+                            _ => return Err(anyhow!("Missing the value of `{key}`")),
+                        }
+                    }
+                    token => return Err(anyhow!("Unexpected {token:?} in the attributes")),
                 }
             }
         }
-        writeln!(f, "}}")
+
+        if chain.len() == 1 {
+            if !["graph", "node", "edge"].contains(&chain[0].as_str()) {
+                declare(&chain[0], label);
+            }
+        } else {
+            for pair in chain.windows(2) {
+                declare(&pair[0], None);
+                declare(&pair[1], None);
+                edges.push((
+                    pair[0].clone(),
+                    pair[1].clone(),
+                    label.clone().unwrap_or_default(),
+                ));
+            }
+        }
+    }
+    ensure!(
+        tokens.next().is_none(),
+        "Unexpected content after the closing `}}`"
+    );
+    Ok((nodes, edges))
+}
+
+/// Build the nodes of a parsed DOT document, the value of a node is its label or its name.
+/// Returns an error if two nodes end up with the same value since they would be merged.
+fn dot_node_ids<Kind>(
+    graph: &mut Graph<String, String, Kind>,
+    nodes: Vec<(String, Option<String>)>,
+) -> Result<HashMap<String, Id>> {
+    nodes
+        .into_iter()
+        .map(|(name, label)| {
+            let value = label.unwrap_or_else(|| name.clone());
+            ensure!(
+                graph.get_id(&value).is_none(),
+                "Two nodes have the value `{value}`, `{name}` is one of them"
+            );
+            Ok((name, graph.insert_value(value)))
+        })
+        .collect()
+}
+
+impl Graph<String, String, Directed> {
+    /// Parse a `digraph` written in the DOT language, for example a graph drawn by hand or
+    /// exported with [Graph::export]. The value of a node is its label or its name, and the
+    /// data of an edge is its label or an empty string. The other attributes are ignored and
+    /// the subgraphs aren't supported. Returns an error if two nodes have the same value.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let graph = Graph::<String, String, aoc::Directed>::parse_dot(r#"
+    ///     digraph {
+    ///         rankdir = LR
+    ///         a -> b -> c [label = 2]
+    ///         c [label = "the end"]
+    ///     }
+    /// "#).unwrap();
+    /// let [a, b, end] = ["a", "b", "the end"].map(|node| graph.get_id(&node.to_string()).unwrap());
+    /// assert_eq!(graph.get_edges(a), Some(&[(b, "2".to_string())][..]));
+    /// assert_eq!(graph.predecessors(end), Some(&[b][..]));
+    ///
+    /// assert!(Graph::<String, String, aoc::Directed>::parse_dot("graph { a -- b }").is_err());
+    /// ```
+    pub fn parse_dot(input: &str) -> Result<Self> {
+        let (nodes, edges) = parse_dot_statements(input, true)?;
+        let mut graph = Self::new_directed();
+        let ids = dot_node_ids(&mut graph, nodes)?;
+        for (from, to, label) in edges {
+            graph.create_edge_with_data(ids[&from], ids[&to], label);
+        }
+        Ok(graph)
+    }
+}
+
+impl Graph<String, String, Undirected> {
+    /// Parse a `graph` written in the DOT language, see the directed version.
+    /// ```
+    /// use aoc::Graph;
+    ///
+    /// let graph = Graph::<String, String>::parse_dot("strict graph g { a -- b; b -- c; c -- a }").unwrap();
+    /// assert_eq!(graph.triangles(), vec![vec!["a", "b", "c"]]);
+    /// ```
+    pub fn parse_dot(input: &str) -> Result<Self> {
+        let (nodes, edges) = parse_dot_statements(input, false)?;
+        let mut graph = Self::new_undirected();
+        let ids = dot_node_ids(&mut graph, nodes)?;
+        for (from, to, label) in edges {
+            graph.create_edge_with_data(ids[&from], ids[&to], label);
+        }
+        Ok(graph)
     }
 }
 
@@ -268,23 +2559,41 @@ mod test {
     use super::*;
     use std::fmt::Debug;
 
+    impl<Value, Edge, Kind: GraphKind> Graph<Value, Edge, Kind> {
+        /// Only the directed graphs track their predecessors.
+        fn ensure_predecessors(&self) {
+            assert_eq!(self.nodes.len(), self.predecessors.len());
+            let mut expected = vec![Vec::new(); self.nodes.len()];
+            for (from, edges) in self.edges.iter().enumerate().filter(|_| Kind::DIRECTED) {
+                for (to, _) in edges {
+                    expected[to.index].push(self.id(from));
+                }
+            }
+            for (mut expected, actual) in expected.into_iter().zip(&self.predecessors) {
+                let mut actual = actual.clone();
+                expected.sort();
+                actual.sort();
+                assert_eq!(expected, actual, "The predecessors are out of sync");
+            }
+        }
+    }
+
     impl<Value, Edge> Graph<Value, Edge, Directed>
     where
-        Value: PartialEq + Eq + Hash + Debug,
+        Value: Clone + PartialEq + Eq + Hash + Debug,
     {
         pub fn ensure_correctness(&self) {
             assert_eq!(self.nodes.len(), self.edges.len());
             let nb_nodes = self.nodes.iter().filter(|node| node.is_some()).count();
             assert_eq!(nb_nodes, self.nodes_ids.len());
+            self.ensure_predecessors();
 
             for (value, id) in &self.nodes_ids {
-                assert_eq!(self.nodes[*id].as_ref().unwrap(), value);
-                for (id, _) in &self.edges[*id] {
-                    assert!(
-                        self.nodes.get(*id).is_some(),
-                        "An edge points to an unexisting node"
-                    );
-                    let node = self.nodes[*id].as_ref().unwrap();
+                assert_eq!(self.get_value(*id).unwrap(), value);
+                for (id, _) in self.get_edges(*id).unwrap() {
+                    let node = self
+                        .get_value(*id)
+                        .expect("An edge points to an unexisting node");
                     assert_eq!(
                         self.nodes_ids.get(node).unwrap(),
                         id,
@@ -297,26 +2606,31 @@ mod test {
 
     impl<Value, Edge> Graph<Value, Edge, Undirected>
     where
-        Value: PartialEq + Eq + Hash + Debug,
+        Value: Clone + PartialEq + Eq + Hash + Debug,
         Edge: PartialEq + Eq + Debug,
     {
         pub fn ensure_correctness(&self) {
             assert_eq!(self.nodes.len(), self.edges.len());
             let nb_nodes = self.nodes.iter().filter(|node| node.is_some()).count();
             assert_eq!(nb_nodes, self.nodes_ids.len());
+            self.ensure_predecessors();
 
             for (value, id) in &self.nodes_ids {
-                assert_eq!(self.nodes[*id].as_ref().unwrap(), value);
-                for (eid, left) in &self.edges[*id] {
+                assert_eq!(self.get_value(*id).unwrap(), value);
+                for (eid, left) in self.get_edges(*id).unwrap() {
+                    let node = self
+                        .get_value(*eid)
+                        .expect("An edge points to an unexisting node");
                     assert_eq!(
-                        &self.edges[*eid].iter().find(|(i, _)| i == id).unwrap().1,
+                        &self
+                            .get_edges(*eid)
+                            .unwrap()
+                            .iter()
+                            .find(|(i, _)| i == id)
+                            .unwrap()
+                            .1,
                         left
                     );
-                    assert!(
-                        self.nodes.get(*eid).is_some(),
-                        "An edge points to an unexisting node"
-                    );
-                    let node = self.nodes[*eid].as_ref().unwrap();
                     assert_eq!(
                         self.nodes_ids.get(node).unwrap(),
                         eid,
@@ -524,4 +2838,649 @@ mod test {
         "###);
         assert_eq!(graph.distance_between(d, c), Some(1));
     }
+
+    #[test]
+    fn weighted_shortest_paths() {
+        let mut graph = Graph::new_undirected();
+        let ids: Vec<_> = (0..6).map(|value| graph.insert_value(value)).collect();
+        for (from, to, cost) in [
+            (0, 1, 7_u8),
+            (0, 2, 9),
+            (0, 5, 14),
+            (1, 2, 10),
+            (1, 3, 15),
+            (2, 3, 11),
+            (2, 5, 2),
+            (3, 4, 6),
+            (4, 5, 9),
+        ] {
+            graph.create_edge_with_data(ids[from], ids[to], cost);
+        }
+        graph.ensure_correctness();
+
+        assert_eq!(
+            graph.shortest_path(ids[0], ids[4]),
+            Some((20, vec![ids[0], ids[2], ids[5], ids[4]]))
+        );
+        assert_eq!(graph.shortest_path(ids[3], ids[3]), Some((0, vec![ids[3]])));
+        assert_eq!(graph.distance_between(ids[0], ids[4]), Some(2));
+
+        let distances = graph.distances_from(ids[0]);
+        assert_eq!(
+            ids.iter().map(|id| distances[id]).collect::<Vec<_>>(),
+            vec![0, 7, 9, 20, 20, 11]
+        );
+
+        let cache = graph.generate_cache();
+        assert_eq!(cache.len(), 36);
+        assert_eq!(cache[&(ids[1], ids[5])], 2);
+
+        let lonely = graph.insert_value(6);
+        assert_eq!(graph.shortest_path(ids[0], lonely), None);
+        assert_eq!(graph.distances_from(lonely), HashMap::from([(lonely, 0)]));
+
+        let infinite = |_, _, cost: &u8| if *cost == 9 { u64::MAX } else { *cost as u64 };
+        assert_eq!(
+            graph.shortest_path_with(ids[0], ids[4], infinite),
+            Some((28, vec![ids[0], ids[1], ids[3], ids[4]]))
+        );
+        assert_eq!(graph.distances_from_with(ids[0], infinite)[&ids[2]], 16);
+    }
+
+    #[test]
+    fn all_pairs() {
+        let mut graph = Graph::new_directed();
+        let ids: Vec<_> = (0..5).map(|value| graph.insert_value(value)).collect();
+        for (from, to, cost) in [(0, 1, 4_u16), (0, 2, 1), (2, 1, 2), (1, 3, 1), (3, 0, 7)] {
+            graph.create_edge_with_data(ids[from], ids[to], cost);
+        }
+        graph.delete_value(ids[4]);
+        graph.ensure_correctness();
+
+        let matrix = graph.floyd_warshall();
+        for (from, row) in ids.iter().zip(&matrix).take(4) {
+            let distances = graph.distances_from(*from);
+            let expected: Vec<_> = ids.iter().map(|to| distances.get(to).copied()).collect();
+            assert_eq!(row, &expected);
+        }
+        assert_eq!(matrix[4], vec![None; 5]);
+        assert_eq!(matrix[3][1], Some(10));
+        assert_eq!(graph.all_pairs_bfs()[3][1], Some(2));
+
+        let infinite = |_, _, cost: &u16| if *cost == 4 { u64::MAX } else { *cost as u64 };
+        let matrix = graph.floyd_warshall_with(infinite);
+        assert_eq!(matrix[0][1], Some(3));
+        assert_eq!(matrix[3][1], Some(10));
+
+        let contracted = graph.contract_with(|value| value % 2 == 1, |_, _, cost| *cost as u64);
+        contracted.ensure_correctness();
+        let (one, three) = (
+            contracted.get_id(&1).unwrap(),
+            contracted.get_id(&3).unwrap(),
+        );
+        assert_eq!(contracted.get_edges(one), Some(&[(three, 1)][..]));
+        assert_eq!(contracted.get_edges(three), Some(&[(one, 10)][..]));
+    }
+
+    #[test]
+    fn strongly_connected_components() {
+        let mut graph = Graph::new_directed();
+        let ids: Vec<_> = (0..9).map(|value| graph.insert_value(value)).collect();
+        for (from, to) in [
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 3),
+            (5, 6),
+            (6, 6),
+            (7, 6),
+        ] {
+            graph.create_edge(ids[from], ids[to]);
+        }
+        graph.delete_value(ids[8]);
+        graph.ensure_correctness();
+        assert!(graph.has_cycle());
+
+        let components = graph.strongly_connected_components();
+        let sorted: Vec<Vec<Id>> = components
+            .iter()
+            .cloned()
+            .map(|mut component| {
+                component.sort();
+                component
+            })
+            .collect();
+        let expected: Vec<Vec<Id>> = [vec![6], vec![3, 4, 5], vec![0, 1, 2], vec![7]]
+            .into_iter()
+            .map(|component| component.into_iter().map(|i| ids[i]).collect())
+            .collect();
+        assert_eq!(sorted, expected);
+
+        let (dag, mapping) = graph.condensation();
+        assert!(!dag.has_cycle());
+        assert_eq!(mapping.len(), 8);
+        let order = dag.topological_sort().unwrap();
+        for (from, to) in [(0, 3), (3, 6), (7, 6)] {
+            let position = |i: usize| {
+                order
+                    .iter()
+                    .position(|component| *component == mapping[&ids[i]])
+            };
+            assert!(position(from) < position(to));
+        }
+        assert!(graph.schedule(2, |_, _| 1, |id, _| id).is_err());
+        assert!(dag.schedule(0, |_, _| 1, |id, _| id).is_err());
+        assert_eq!(
+            dag.schedule(1, |_, values| values.len() as u64, |id, _| id)
+                .unwrap()
+                .0,
+            8
+        );
+    }
+
+    #[test]
+    fn cuts() {
+        let input = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+        let mut graph = Graph::new_undirected();
+        for line in input.lines() {
+            let (left, rights) = line.split_once(": ").unwrap();
+            let left = graph.insert_value(left);
+            for right in rights.split(' ') {
+                let right = graph.insert_value(right);
+                graph.create_edge(left, right);
+            }
+        }
+        graph.ensure_correctness();
+        assert_eq!(graph.connected_components().len(), 1);
+        assert!(graph.bridges().is_empty());
+        assert!(graph.articulation_points().is_empty());
+
+        let MinCut { edges: cut, sizes } = graph.min_cut().unwrap();
+        let mut names: Vec<_> = cut
+            .iter()
+            .map(|(a, b)| {
+                let mut names = [*graph.get_value(*a).unwrap(), *graph.get_value(*b).unwrap()];
+                names.sort();
+                names
+            })
+            .collect();
+        names.sort();
+        assert_eq!(names, vec![["bvb", "cmg"], ["hfx", "pzl"], ["jqt", "nvd"]]);
+        assert_eq!(sizes, (6, 9));
+
+        for (a, b) in cut {
+            graph.delete_edge(a, b);
+        }
+        graph.ensure_correctness();
+        let components = graph.connected_components();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].len() * components[1].len(), 54);
+        assert_eq!(
+            graph.min_cut().unwrap(),
+            MinCut {
+                edges: vec![],
+                sizes: (6, 9)
+            }
+        );
+
+        let lonely = graph.insert_value("lonely");
+        assert_eq!(graph.connected_components().len(), 3);
+        assert_eq!(graph.connected_components()[2], vec![lonely]);
+    }
+
+    #[test]
+    fn articulation_points() {
+        let mut graph = Graph::new_undirected();
+        let ids: Vec<_> = (0..7).map(|value| graph.insert_value(value)).collect();
+        // A star around 0, with a triangle hanging on 1 and a tail after 4.
+        for (a, b) in [(0, 1), (0, 2), (0, 3), (1, 4), (4, 5), (5, 1), (4, 6)] {
+            graph.create_edge(ids[a], ids[b]);
+        }
+        assert_eq!(graph.articulation_points(), vec![ids[0], ids[1], ids[4]]);
+        assert_eq!(
+            graph.bridges(),
+            [(0, 1), (0, 2), (0, 3), (4, 6)].map(|(a, b)| (ids[a], ids[b]))
+        );
+
+        let mut single: Graph<_> = Graph::new_undirected();
+        single.insert_value(0);
+        assert_eq!(single.min_cut(), None);
+    }
+
+    #[test]
+    fn lan_party() {
+        let input =
+            "kh-tc qp-kh de-cg ka-co yn-aq qp-ub cg-tb vc-aq tb-ka wh-tc yn-cg kh-ub ta-co \
+            de-co tc-td tb-wq wh-td ta-ka td-qp aq-cg wq-ub ub-vc de-ta wq-aq wq-vc wh-yn ka-de \
+            kh-ta co-tc wh-qp tb-vc td-yn";
+        let mut graph = Graph::new_undirected();
+        for link in input.split_whitespace() {
+            let (a, b) = link.split_once('-').unwrap();
+            let (a, b) = (graph.insert_value(a), graph.insert_value(b));
+            graph.create_edge(a, b);
+        }
+
+        let triangles = graph.triangles();
+        assert_eq!(triangles.len(), 12);
+        assert_eq!(
+            triangles
+                .iter()
+                .filter(|triangle| triangle.iter().any(|computer| computer.starts_with('t')))
+                .count(),
+            7
+        );
+        assert!(triangles.contains(&vec!["co", "de", "ta"]));
+        assert_eq!(graph.cliques_of_size(4), vec![vec!["co", "de", "ka", "ta"]]);
+        assert_eq!(graph.maximum_clique().join(","), "co,de,ka,ta");
+
+        let independent = graph.maximum_independent_set();
+        for (i, a) in independent.iter().enumerate() {
+            for b in &independent[i + 1..] {
+                let (a, b) = (graph.get_id(a).unwrap(), graph.get_id(b).unwrap());
+                assert!(graph.get_edges(a).unwrap().iter().all(|(id, _)| *id != b));
+            }
+        }
+        assert!(graph.cliques_of_size(5).is_empty());
+    }
+
+    #[test]
+    fn from_grid_and_text() {
+        let grid = Grid::from(vec![
+            vec!['.', '.', '.', '#'],
+            vec!['.', '#', '.', '.'],
+            vec!['.', '.', '.', '#'],
+        ]);
+        let graph = Graph::from_grid(&grid, |cell| *cell == '.', &Direction8::ALL4);
+        graph.ensure_correctness();
+        assert_eq!(graph.values().count(), 9);
+        assert_eq!(graph.connected_components().len(), 1);
+        assert_eq!(
+            graph.bridges(),
+            vec![(
+                graph.get_id(&Coord::at(2, 1)).unwrap(),
+                graph.get_id(&Coord::at(3, 1)).unwrap()
+            )]
+        );
+
+        let contracted = Graph::from_grid_contracted(&grid, |cell| *cell == '.', &Direction8::ALL4);
+        contracted.ensure_correctness();
+        let (junction, dead_end) = (
+            contracted.get_id(&Coord::at(2, 1)).unwrap(),
+            contracted.get_id(&Coord::at(3, 1)).unwrap(),
+        );
+        assert_eq!(contracted.values().count(), 2);
+        assert_eq!(contracted.get_edges(junction), Some(&[(dead_end, 1)][..]));
+
+        let graph = Graph::<String>::parse_adjacency("a: b c\nb: c\n\nd: a", ":").unwrap();
+        graph.ensure_correctness();
+        assert_eq!(graph.triangles(), vec![vec!["a", "b", "c"]]);
+        assert_eq!(graph.bridges().len(), 1);
+        assert!(Graph::<String>::parse_adjacency("a b", ":").is_err());
+
+        let graph = Graph::<String, (), Directed>::parse_edges("a -> b\nb -> c", "->").unwrap();
+        graph.ensure_correctness();
+        assert!(!graph.has_cycle());
+    }
+
+    fn brute_force_longest<Kind>(
+        graph: &Graph<usize, u64, Kind>,
+        current: Id,
+        end: Id,
+        visited: &mut HashSet<Id>,
+    ) -> Option<u64> {
+        if current == end {
+            return Some(0);
+        }
+        let mut longest = None;
+        for (next, cost) in graph.get_edges(current).unwrap() {
+            if visited.insert(*next) {
+                let length =
+                    brute_force_longest(graph, *next, end, visited).map(|length| length + cost);
+                longest = longest.max(length);
+                visited.remove(next);
+            }
+        }
+        longest
+    }
+
+    #[test]
+    fn longest_simple_path() {
+        // A small linear congruential generator to build reproducible graphs.
+        let mut seed = 42_u64;
+        let mut random = move |max: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % max
+        };
+
+        for _ in 0..20 {
+            let mut dag = Graph::new_directed();
+            let mut cyclic = Graph::new_undirected();
+            let ids: Vec<_> = (0..12)
+                .map(|value| (dag.insert_value(value), cyclic.insert_value(value)))
+                .collect();
+            for _ in 0..24 {
+                let (a, b) = (random(12) as usize, random(12) as usize);
+                let cost = random(10);
+                if a < b {
+                    dag.create_edge_with_data(ids[a].0, ids[b].0, cost);
+                }
+                if a != b {
+                    cyclic.create_edge_with_data(ids[a].1, ids[b].1, cost);
+                }
+            }
+            assert!(!dag.has_cycle());
+
+            for (start, end) in [(0, 11), (2, 7), (5, 5)] {
+                let (start, end) = (ids[start], ids[end]);
+                let expected =
+                    brute_force_longest(&dag, start.0, end.0, &mut HashSet::from([start.0]));
+                assert_eq!(dag.longest_simple_path(start.0, end.0), expected);
+                let expected =
+                    brute_force_longest(&cyclic, start.1, end.1, &mut HashSet::from([start.1]));
+                assert_eq!(cyclic.longest_simple_path(start.1, end.1), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn longest_simple_path_in_a_big_cyclic_graph() {
+        let mut directed = Graph::new_directed();
+        let mut undirected = Graph::new_undirected();
+        // Leave deleted slots in the middle of the nodes.
+        for value in 0..300 {
+            let ids = (directed.insert_value(value), undirected.insert_value(value));
+            if value % 3 == 1 {
+                directed.delete_value(ids.0);
+                undirected.delete_value(ids.1);
+            }
+        }
+        let ids: Vec<_> = (0..300)
+            .filter(|value| value % 3 != 1)
+            .map(|value| {
+                (
+                    directed.get_id(&value).unwrap(),
+                    undirected.get_id(&value).unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(ids.len(), 200);
+        for pair in ids.windows(2) {
+            directed.create_edge_with_data(pair[0].0, pair[1].0, 1_u64);
+            undirected.create_edge_with_data(pair[0].1, pair[1].1, 1_u64);
+        }
+        // Close the loop and add a few shortcuts.
+        let (first, last) = (ids[0], ids[199]);
+        directed.create_edge_with_data(last.0, first.0, 1);
+        for (from, to) in [(10, 50), (150, 190)] {
+            directed.create_edge_with_data(ids[from].0, ids[to].0, 100);
+            undirected.create_edge_with_data(ids[from].1, ids[to].1, 100);
+        }
+        assert!(directed.has_cycle());
+
+        // Both shortcuts skip 40 edges of cost 1 for an edge of cost 100.
+        assert_eq!(
+            directed.longest_simple_path(first.0, last.0),
+            Some(199 + 2 * 60)
+        );
+        assert_eq!(
+            directed.longest_simple_path(ids[20].0, ids[10].0),
+            Some(190 + 60)
+        );
+        assert_eq!(
+            undirected.longest_simple_path(first.1, last.1),
+            Some(199 + 2 * 60)
+        );
+        assert_eq!(
+            undirected.longest_simple_path(ids[30].1, ids[20].1),
+            Some(20 + 100 + 10)
+        );
+    }
+
+    #[test]
+    fn stale_ids() {
+        let mut graph = Graph::new_directed();
+        let ids: Vec<_> = (0..4).map(|value| graph.insert_value(value)).collect();
+        for (from, to) in [(0, 1), (1, 2), (2, 3), (3, 0), (0, 2)] {
+            graph.create_edge(ids[from], ids[to]);
+        }
+        assert_eq!((graph.node_count(), graph.edge_count()), (4, 5));
+
+        assert!(graph.delete_value(ids[1]));
+        assert!(!graph.delete_value(ids[1]));
+        assert!(!graph.delete_by_value(&1));
+        graph.ensure_correctness();
+        assert_eq!((graph.node_count(), graph.edge_count()), (3, 3));
+        assert_eq!(graph.get_value(ids[1]), None);
+        assert_eq!(graph.get_edges(ids[1]), None);
+        assert_eq!(graph.delete_edge(ids[0], ids[1]), None);
+        assert_eq!(graph.distance_between(ids[1], ids[2]), None);
+        assert!(graph.distances_from_with(ids[1], |_, _, _| 1).is_empty());
+
+        // Inserting the value again creates a new node, the old id stays dead.
+        let one = graph.insert_value(1);
+        assert_ne!(one, ids[1]);
+        assert_eq!(graph.get_value(ids[1]), None);
+
+        let new_ids = graph.compact();
+        graph.ensure_correctness();
+        assert_eq!(new_ids.len(), 4);
+        assert_eq!((graph.node_count(), graph.edge_count()), (4, 3));
+        assert!(ids.iter().all(|id| graph.get_value(*id).is_none()));
+        assert_eq!(new_ids[&one].index(), 3);
+        assert_eq!(graph.get_value(new_ids[&ids[3]]), Some(&3));
+        assert_eq!(
+            graph.shortest_path_with(new_ids[&ids[2]], new_ids[&ids[0]], |_, _, _| 1),
+            Some((
+                2,
+                vec![new_ids[&ids[2]], new_ids[&ids[3]], new_ids[&ids[0]]]
+            ))
+        );
+
+        let mut undirected = Graph::new_undirected();
+        let (a, b) = (undirected.insert_value('a'), undirected.insert_value('b'));
+        undirected.create_edge(a, b);
+        undirected.create_edge(a, a);
+        assert_eq!(undirected.edge_count(), 2);
+    }
+
+    #[test]
+    fn traversals() {
+        let mut graph = Graph::new_directed();
+        let ids: Vec<_> = (0..8).map(|value| graph.insert_value(value)).collect();
+        for (from, to) in [
+            (0, 1),
+            (0, 2),
+            (1, 3),
+            (2, 3),
+            (3, 4),
+            (4, 1),
+            (5, 0),
+            (6, 6),
+            (6, 7),
+        ] {
+            graph.create_edge(ids[from], ids[to]);
+        }
+        graph.ensure_correctness();
+
+        for (id, depth) in graph.bfs(ids[0]) {
+            assert_eq!(graph.distance_between(ids[0], id), Some(depth));
+        }
+        let reachable = graph.reachable_from(ids[0]);
+        assert_eq!(reachable, ids[..5].iter().copied().collect());
+        assert_eq!(graph.dfs(ids[0]).count(), 5);
+        assert_eq!(
+            graph.reachable_from(ids[6]),
+            HashSet::from([ids[6], ids[7]])
+        );
+
+        let events: Vec<_> = graph.dfs_events(ids[0]).collect();
+        assert_eq!(events.len(), 10);
+        let mut open = Vec::new();
+        for event in events {
+            match event {
+                DfsEvent::Pre(id, depth) => {
+                    assert_eq!(depth, open.len());
+                    open.push(id);
+                }
+                DfsEvent::Post(id, depth) => {
+                    assert_eq!(open.pop(), Some(id));
+                    assert_eq!(depth, open.len());
+                }
+            }
+        }
+        let post_order: Vec<_> = graph
+            .dfs_events(ids[0])
+            .filter_map(|event| match event {
+                DfsEvent::Post(id, _) => Some(id),
+                DfsEvent::Pre(..) => None,
+            })
+            .collect();
+        assert_eq!(post_order, [4, 3, 1, 2, 0].map(|i| ids[i]));
+
+        assert_eq!(graph.predecessors(ids[3]), Some(&[ids[1], ids[2]][..]));
+        assert_eq!(graph.predecessors(ids[6]), Some(&[ids[6]][..]));
+        graph.delete_edge(ids[2], ids[3]);
+        graph.delete_value(ids[0]);
+        graph.ensure_correctness();
+        assert_eq!(graph.predecessors(ids[3]), Some(&[ids[1]][..]));
+        assert_eq!(graph.predecessors(ids[1]), Some(&[ids[4]][..]));
+        assert_eq!(graph.predecessors(ids[0]), None);
+        assert_eq!(graph.bfs(ids[0]).count(), 0);
+
+        let new_ids = graph.compact();
+        graph.ensure_correctness();
+        assert_eq!(
+            graph.predecessors(new_ids[&ids[7]]),
+            Some(&[new_ids[&ids[6]]][..])
+        );
+
+        let mut undirected = Graph::new_undirected();
+        let ids: Vec<_> = (0..4).map(|value| undirected.insert_value(value)).collect();
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (2, 3)] {
+            undirected.create_edge(ids[a], ids[b]);
+        }
+        undirected.delete_edge(ids[0], ids[2]);
+        undirected.ensure_correctness();
+        let depths: Vec<_> = undirected.bfs(ids[3]).map(|(_, depth)| depth).collect();
+        assert_eq!(depths, vec![0, 1, 2, 3]);
+        undirected.delete_value(ids[1]);
+        undirected.ensure_correctness();
+        assert_eq!(undirected.reachable_from(ids[0]), HashSet::from([ids[0]]));
+        undirected.contract(|_| true).ensure_correctness();
+    }
+
+    #[test]
+    fn export_and_parse_dot() {
+        let mut graph = Graph::new_undirected();
+        let ids: Vec<_> = ["a", "b \"quoted\"", "c"]
+            .into_iter()
+            .map(|value| graph.insert_value(value))
+            .collect();
+        graph.create_edge_with_data(ids[0], ids[1], 5);
+        graph.create_edge_with_data(ids[1], ids[2], 7);
+        graph.create_edge_with_data(ids[2], ids[2], 1);
+
+        let export = graph
+            .export()
+            .with_edge_labels()
+            .with_node_style(|id, _| NodeStyle {
+                color: (id == ids[2]).then(|| "blue".to_string()),
+                shape: Some("circle".to_string()),
+            })
+            .with_path(&[ids[2], ids[1]]);
+        insta::assert_snapshot!(export.to_dot(), @r###"
+        graph a {
+        	 "0" [label = "a", shape = "circle"]
+        	 "1" [label = "b \"quoted\"", color = "red", shape = "circle", penwidth = "2"]
+        	 "2" [label = "c", color = "red", shape = "circle", penwidth = "2"]
+        	 "0" -- "1" [label = "5"]
+        	 "1" -- "2" [label = "7", color = "red", penwidth = "2"]
+        	 "2" -- "2" [label = "1"]
+        }
+        "###);
+        insta::assert_snapshot!(export.to_mermaid(), @r###"
+        flowchart LR
+            n0(("a"))
+            n1(("b #quot;quoted#quot;"))
+            n2(("c"))
+            n0 ---|"5"| n1
+            n1 ---|"7"| n2
+            n2 ---|"1"| n2
+            style n1 stroke:red,stroke-width:2px
+            style n2 stroke:red,stroke-width:2px
+            linkStyle 1 stroke:red,stroke-width:2px
+        "###);
+
+        let parsed = Graph::<String, String>::parse_dot(&export.to_dot()).unwrap();
+        parsed.ensure_correctness();
+        assert_eq!(parsed.node_count(), 3);
+        assert_eq!(parsed.edge_count(), 3);
+        let [a, b] = ["a", "b \"quoted\""].map(|value| parsed.get_id(&value.to_string()).unwrap());
+        assert_eq!(parsed.get_edges(a), Some(&[(b, "5".to_string())][..]));
+
+        let parsed = Graph::<String, String, Directed>::parse_dot(
+            "// A hand drawn graph
+            digraph test {
+                node [shape = box]; /* defaults are ignored */
+                start -> \"the middle\" -> end -> start
+                start -> end [color = red, label = \"a shortcut\"]
+            }",
+        )
+        .unwrap();
+        parsed.ensure_correctness();
+        let [start, end] = ["start", "end"].map(|value| parsed.get_id(&value.to_string()).unwrap());
+        assert_eq!(parsed.node_count(), 3);
+        assert_eq!(parsed.edge_count(), 4);
+        assert_eq!(parsed.predecessors(start), Some(&[end][..]));
+        assert_eq!(
+            parsed.get_edges(start).unwrap()[1],
+            (end, "a shortcut".to_string())
+        );
+
+        for invalid in [
+            "digraph { a -> b }",
+            "graph { a -> b }",
+            "graph { a -- }",
+            "graph { a [label] }",
+            "graph { a -- b",
+            "graph { \"a }",
+            "graph { a } b",
+            "graph { a -- b # not a comment }",
+            "graph { a [label = b]; b }",
+        ] {
+            assert!(
+                Graph::<String, String>::parse_dot(invalid).is_err(),
+                "{invalid}"
+            );
+        }
+
+        let mut graph = Graph::new_directed();
+        let ids = [r"C:\dir\", r#"a \"b\" c"#].map(|value| graph.insert_value(value.to_string()));
+        graph.create_edge_with_data(ids[0], ids[1], r"\".to_string());
+        let parsed = Graph::<String, String, Directed>::parse_dot(&format!(
+            "# a preprocessor line\n  #another one\n{}",
+            graph.export().with_edge_labels().to_dot()
+        ))
+        .unwrap();
+        let [from, to] =
+            [r"C:\dir\", r#"a \"b\" c"#].map(|value| parsed.get_id(&value.to_string()).unwrap());
+        assert_eq!(parsed.get_edges(from), Some(&[(to, r"\".to_string())][..]));
+    }
 }
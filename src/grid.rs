@@ -2,8 +2,8 @@
 
 use std::fmt::Display;
 
-use crate::Coord;
-use anyhow::Result;
+use crate::{Coord, Range};
+use anyhow::{ensure, Result};
 
 /// A 2D [Grid] with a lot of fancy methods on it.
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
@@ -342,8 +342,9 @@ impl<T> Grid<T> {
         self.data.iter_mut().rev()
     }
 
-    /// Return an [Iterator] of all the element in the [Grid] from one [Coord] to another.
-    /// Can return an error if the starting position is situated *AFTER* the end position. See [Coord::to].
+    /// Return an [Iterator] of all the element in the [Grid] from one [Coord] to another, line by line.
+    /// `from` can be any corner of the rectangle, each line is walked toward `to`. See [Range::between].
+    /// Returns an error if one of the corners is outside of the grid.
     ///
     /// See also [Grid::through_mut].
     /// # Example
@@ -368,7 +369,10 @@ impl<T> Grid<T> {
     /// assert_eq!(iter.next(), Some(&'s'));
     /// assert_eq!(iter.next(), None);
     ///
-    /// let result = grid.through(aoc::Coord::at(2, 2), aoc::Coord::at(1, 1));
+    /// let backward: String = grid.through(aoc::Coord::at(2, 2), aoc::Coord::at(1, 1)).unwrap().collect();
+    /// assert_eq!(backward, "mlhg");
+    ///
+    /// let result = grid.through(aoc::Coord::at(2, 2), aoc::Coord::at(5, 1));
     /// assert!(result.is_err());
     /// ```
     pub fn through(
@@ -376,11 +380,17 @@ impl<T> Grid<T> {
         from: Coord<usize>,
         to: Coord<usize>,
     ) -> Result<impl Iterator<Item = &T>> {
-        Ok(from.to(to)?.map(move |coord| &self[coord]))
+        ensure!(
+            self.contains(&from) && self.contains(&to),
+            "Can't walk from {:?} to {:?} outside of the grid",
+            from,
+            to
+        );
+        Ok(Range::between(from, to).map(move |coord| &self[coord]))
     }
 
     /// Return a mutable [Iterator] of all the elements in the grid from one [Coord] to another.
-    /// Can return an error if the starting position is situated *AFTER* the end position. See [Coord::to].
+    /// Returns an error if one of the corners is outside of the grid.
     ///
     /// See also [Grid::through].
     /// # Example
@@ -410,8 +420,13 @@ impl<T> Grid<T> {
         from: Coord<usize>,
         to: Coord<usize>,
     ) -> Result<impl Iterator<Item = &mut T>> {
-        Ok(from
-            .to(to)?
+        ensure!(
+            self.contains(&from) && self.contains(&to),
+            "Can't walk from {:?} to {:?} outside of the grid",
+            from,
+            to
+        );
+        Ok(Range::between(from, to)
             .map(move |coord| unsafe { std::mem::transmute(&mut self[coord]) }))
     }
 
@@ -438,7 +453,7 @@ impl<T> Grid<T> {
     /// ```
     pub fn borders(&self) -> impl Iterator<Item = &T> + '_ {
         match (self.height(), self.width()) {
-            (0 | 1 | 2, _) | (_, 0 | 1) => Box::new(self.iter()) as Box<dyn Iterator<Item = &T>>,
+            (0..=2, _) | (_, 0 | 1) => Box::new(self.iter()) as Box<dyn Iterator<Item = &T>>,
             (height, width) => Box::new(
                 self.through(Coord::at(0, 0), Coord::at(width - 1, 0))
                     .unwrap()
@@ -482,7 +497,7 @@ impl<T> Grid<T> {
     /// ```
     pub fn borders_coord(&self) -> impl Iterator<Item = Coord<usize>> + '_ {
         match (self.height(), self.width()) {
-            (0 | 1 | 2, _) | (_, 0 | 1) => Box::new(
+            (0..=2, _) | (_, 0 | 1) => Box::new(
                 Coord::at(0, 0)
                     .to(Coord::at(self.height(), self.width()))
                     .unwrap(),
@@ -609,7 +624,7 @@ impl<T> Grid<T> {
             .map(|line| {
                 line.iter()
                     .position(|el| !to_trim(el))
-                    .unwrap_or_else(|| line.len())
+                    .unwrap_or(line.len())
             })
             .min()
             .unwrap_or_default();
@@ -645,7 +660,7 @@ impl<T> Grid<T> {
                 line.iter()
                     .rev()
                     .position(|el| !to_trim(el))
-                    .unwrap_or_else(|| line.len())
+                    .unwrap_or(line.len())
             })
             .min()
             .unwrap_or_default();
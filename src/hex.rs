@@ -0,0 +1,654 @@
+//! Define a coordinate on an hexagonal grid and all kind of operations on it.
+//!
+//! The coordinates are stored in the axial form `(q, r)`, the third cube coordinate `s` is always
+//! computed as `-q - r`. See <https://www.redblobgames.com/grids/hexagons/> for a deep explanation.
+//!
+//! An hexagonal grid can be drawn with its hexagons pointing up ([HexLayout::Pointy], they
+//! have neighbors on their `e` and `w` sides) or with their flat side up ([HexLayout::Flat],
+//! they have neighbors on their `n` and `s` sides). The coordinates and distances are the same
+//! in both layouts, only the name of the six directions changes.
+
+use crate::num;
+use anyhow::{bail, ensure, Error, Result};
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::ops;
+use std::str::FromStr;
+
+/// Define an hexagonal `Coord`inate in the axial form.
+#[derive(Debug, Default, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct HexCoord<I = isize> {
+    pub q: I,
+    pub r: I,
+}
+
+impl<I> HexCoord<I> {
+    /// Create an hexagonal coordinate from its axial form.
+    /// ```
+    /// use aoc::HexCoord;
+    ///
+    /// let coord = HexCoord::at(2, -1);
+    ///
+    /// assert_eq!(coord.q, 2);
+    /// assert_eq!(coord.r, -1);
+    /// ```
+    pub fn at(q: I, r: I) -> Self {
+        Self { q, r }
+    }
+}
+
+impl<I> HexCoord<I>
+where
+    I: ops::Neg<Output = I> + ops::Sub<Output = I> + ops::Add<Output = I> + num::Zero + Eq + Copy,
+{
+    /// Create an hexagonal coordinate from its cube form.
+    /// Returns an error if `q + r + s` is not equal to zero.
+    /// ```
+    /// use aoc::HexCoord;
+    ///
+    /// assert_eq!(HexCoord::from_cube(1, -3, 2).unwrap(), HexCoord::at(1, -3));
+    /// assert!(HexCoord::from_cube(1, 1, 1).is_err());
+    /// ```
+    pub fn from_cube(q: I, r: I, s: I) -> Result<Self> {
+        ensure!(
+            q + r + s == I::zero(),
+            "The sum of the cube coordinates must be equal to zero"
+        );
+        Ok(Self { q, r })
+    }
+
+    /// Return the third cube coordinate.
+    /// ```
+    /// use aoc::HexCoord;
+    ///
+    /// assert_eq!(HexCoord::at(1, -3).s(), 2);
+    /// ```
+    pub fn s(&self) -> I {
+        -self.q - self.r
+    }
+
+    /// Return the cube form `(q, r, s)` of the coordinate.
+    /// ```
+    /// use aoc::HexCoord;
+    ///
+    /// assert_eq!(HexCoord::at(1, -3).cube(), (1, -3, 2));
+    /// ```
+    pub fn cube(&self) -> (I, I, I) {
+        (self.q, self.r, self.s())
+    }
+
+    /// Rotate the coordinate clockwise by 60 degrees around the origin.
+    /// ```
+    /// use aoc::HexCoord;
+    ///
+    /// let base = HexCoord::at(1, 0);
+    ///
+    /// assert_eq!(base.rotate_clockwise(), HexCoord::at(0, 1));
+    /// assert_eq!(base.rotate_clockwise().rotate_clockwise(), HexCoord::at(-1, 1));
+    /// assert_eq!(base.rotate_clockwise_n(6), base);
+    /// ```
+    pub fn rotate_clockwise(&self) -> Self {
+        Self::at(-self.r, -self.s())
+    }
+
+    /// Rotate the coordinate clockwise by 60 degrees around the origin n times.
+    pub fn rotate_clockwise_n(&self, n: usize) -> Self {
+        (0..n % 6).fold(*self, |coord, _| coord.rotate_clockwise())
+    }
+
+    /// Rotate the coordinate counter clockwise by 60 degrees around the origin.
+    /// ```
+    /// use aoc::HexCoord;
+    ///
+    /// let base = HexCoord::at(1, 0);
+    ///
+    /// assert_eq!(base.rotate_counter_clockwise(), HexCoord::at(1, -1));
+    /// assert_eq!(base.rotate_counter_clockwise().rotate_clockwise(), base);
+    /// assert_eq!(base.rotate_counter_clockwise_n(6), base);
+    /// ```
+    pub fn rotate_counter_clockwise(&self) -> Self {
+        Self::at(-self.s(), -self.q)
+    }
+
+    /// Rotate the coordinate counter clockwise by 60 degrees around the origin n times.
+    pub fn rotate_counter_clockwise_n(&self, n: usize) -> Self {
+        (0..n % 6).fold(*self, |coord, _| coord.rotate_counter_clockwise())
+    }
+
+    /// Rotate the coordinate clockwise by 60 degrees around another coordinate n times.
+    /// ```
+    /// use aoc::HexCoord;
+    ///
+    /// let center = HexCoord::at(2, 2);
+    ///
+    /// assert_eq!(HexCoord::at(3, 2).rotate_around(center, 1), HexCoord::at(2, 3));
+    /// assert_eq!(center.rotate_around(center, 1), center);
+    /// ```
+    pub fn rotate_around(&self, center: Self, n: usize) -> Self {
+        (*self - center).rotate_clockwise_n(n) + center
+    }
+}
+
+impl<I> HexCoord<I>
+where
+    I: ops::Neg<Output = I>
+        + ops::Sub<Output = I>
+        + ops::Add<Output = I>
+        + ops::Mul<Output = I>
+        + num::Zero
+        + num::One
+        + Ord
+        + Copy,
+{
+    /// Compute the number of steps needed to go from one hexagon to another.
+    /// ```
+    /// use aoc::HexCoord;
+    ///
+    /// let orig = HexCoord::default();
+    ///
+    /// assert_eq!(orig.distance_from(&HexCoord::at(1, 0)), 1);
+    /// assert_eq!(orig.distance_from(&HexCoord::at(1, -1)), 1);
+    /// assert_eq!(orig.distance_from(&HexCoord::at(1, 1)), 2);
+    /// assert_eq!(orig.distance_from(&HexCoord::at(3, -5)), 5);
+    /// ```
+    pub fn distance_from(&self, other: &Self) -> I {
+        let abs = |n: I| if n < I::zero() { -n } else { n };
+        let diff = *self - *other;
+
+        abs(diff.q).max(abs(diff.r)).max(abs(diff.s()))
+    }
+
+    /// Compute the number of steps needed to go from the origin to this hexagon.
+    pub fn distance_from_base(&self) -> I {
+        Self::at(I::zero(), I::zero()).distance_from(self)
+    }
+
+    /// Returns an iterator over the six neighbors of the coordinate.
+    /// The neighbors are returned clockwise, starting from the one with the greatest `q`.
+    /// ```
+    /// use aoc::HexCoord;
+    ///
+    /// let mut coords = HexCoord::at(0, 0).neighbors();
+    ///
+    /// assert_eq!(coords.next(), Some(HexCoord::at(1, 0)));
+    /// assert_eq!(coords.next(), Some(HexCoord::at(0, 1)));
+    /// assert_eq!(coords.next(), Some(HexCoord::at(-1, 1)));
+    /// assert_eq!(coords.next(), Some(HexCoord::at(-1, 0)));
+    /// assert_eq!(coords.next(), Some(HexCoord::at(0, -1)));
+    /// assert_eq!(coords.next(), Some(HexCoord::at(1, -1)));
+    /// assert_eq!(coords.next(), None);
+    /// ```
+    pub fn neighbors(&self) -> impl Iterator<Item = HexCoord<I>> {
+        let base = *self;
+        let unit = Self::at(I::one(), I::zero());
+        (0..6).map(move |n| base + unit.rotate_clockwise_n(n))
+    }
+
+    /// Returns all the coordinates at exactly `distance` steps of `self`.
+    /// ```
+    /// use aoc::HexCoord;
+    ///
+    /// assert_eq!(HexCoord::at(0, 0).ring(0), vec![HexCoord::at(0, 0)]);
+    /// assert_eq!(HexCoord::at(0, 0).ring(1).len(), 6);
+    /// assert_eq!(HexCoord::at(0, 0).ring(3).len(), 18);
+    /// ```
+    pub fn ring(&self, distance: I) -> Vec<HexCoord<I>> {
+        if distance == I::zero() {
+            return vec![*self];
+        }
+        let unit = Self::at(I::one(), I::zero());
+        let mut current = *self + unit.rotate_counter_clockwise_n(2) * distance;
+        let mut ret = Vec::new();
+
+        for side in 0..6 {
+            let step = unit.rotate_clockwise_n(side);
+            let mut walked = I::zero();
+            while walked < distance {
+                ret.push(current);
+                current += step;
+                walked = walked + I::one();
+            }
+        }
+
+        ret
+    }
+
+    /// Move the coordinate one step in a [HexDirection] according to a [HexLayout].
+    /// Returns an error if the direction doesn't exist in this layout.
+    /// ```
+    /// use aoc::{HexCoord, HexDirection, HexLayout};
+    ///
+    /// let coord = HexCoord::at(0, 0);
+    ///
+    /// assert_eq!(coord.step(HexDirection::East, HexLayout::Pointy).unwrap(), HexCoord::at(1, 0));
+    /// assert_eq!(coord.step(HexDirection::North, HexLayout::Flat).unwrap(), HexCoord::at(0, -1));
+    /// assert!(coord.step(HexDirection::North, HexLayout::Pointy).is_err());
+    /// ```
+    pub fn step(&self, direction: HexDirection, layout: HexLayout) -> Result<Self> {
+        Ok(*self + direction.to_hex_coord(layout)?)
+    }
+}
+
+impl<I: ops::Add<Output = I>> ops::Add for HexCoord<I> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::at(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl<I: ops::Sub<Output = I>> ops::Sub for HexCoord<I> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::at(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl<I: ops::Mul<Output = I> + Clone> ops::Mul<I> for HexCoord<I> {
+    type Output = Self;
+
+    fn mul(self, n: I) -> Self {
+        Self::at(self.q * n.clone(), self.r * n)
+    }
+}
+
+impl<I, T> ops::AddAssign<T> for HexCoord<I>
+where
+    Self: ops::Add<T, Output = Self> + Clone,
+{
+    fn add_assign(&mut self, other: T) {
+        *self = self.clone() + other
+    }
+}
+
+impl<I, T> ops::SubAssign<T> for HexCoord<I>
+where
+    Self: ops::Sub<T, Output = Self> + Clone,
+{
+    fn sub_assign(&mut self, other: T) {
+        *self = self.clone() - other
+    }
+}
+
+/// The way the hexagons are drawn.
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq)]
+pub enum HexLayout {
+    /// The hexagons have a corner on their top, the neighbors are on the
+    /// `e`, `se`, `sw`, `w`, `nw` and `ne` sides.
+    Pointy,
+    /// The hexagons have a flat top, the neighbors are on the
+    /// `n`, `ne`, `se`, `s`, `sw` and `nw` sides.
+    Flat,
+}
+
+/// The default layout is the `Pointy` one
+impl Default for HexLayout {
+    fn default() -> Self {
+        Self::Pointy
+    }
+}
+
+impl HexLayout {
+    /// Return the six directions available in this layout, clockwise.
+    /// ```
+    /// use aoc::{HexDirection, HexLayout};
+    ///
+    /// assert_eq!(HexLayout::Pointy.directions()[0], HexDirection::East);
+    /// assert_eq!(HexLayout::Flat.directions()[0], HexDirection::North);
+    /// ```
+    pub fn directions(self) -> [HexDirection; 6] {
+        use HexDirection::*;
+
+        match self {
+            HexLayout::Pointy => [East, SouthEast, SouthWest, West, NorthWest, NorthEast],
+            HexLayout::Flat => [North, NorthEast, SouthEast, South, SouthWest, NorthWest],
+        }
+    }
+}
+
+/// Represent a direction on an hexagonal grid.
+/// Depending on the [HexLayout] the `North` and `South` or the `East` and `West` directions
+/// doesn't exist.
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq)]
+pub enum HexDirection {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl HexDirection {
+    /// Return the offset to apply to an [HexCoord] to move in this direction.
+    /// Returns an error if the direction doesn't exist in this layout.
+    /// ```
+    /// use aoc::{HexCoord, HexDirection, HexLayout};
+    ///
+    /// assert_eq!(HexDirection::NorthWest.to_hex_coord(HexLayout::Pointy).unwrap(), HexCoord::<isize>::at(0, -1));
+    /// assert_eq!(HexDirection::NorthWest.to_hex_coord(HexLayout::Flat).unwrap(), HexCoord::<isize>::at(-1, 0));
+    /// assert!(HexDirection::West.to_hex_coord::<isize>(HexLayout::Flat).is_err());
+    /// ```
+    pub fn to_hex_coord<I>(self, layout: HexLayout) -> Result<HexCoord<I>>
+    where
+        I: ops::Neg<Output = I> + num::Zero + num::One,
+    {
+        use HexDirection::*;
+        use HexLayout::*;
+
+        let (zero, one) = (I::zero, I::one);
+        Ok(match (self, layout) {
+            (NorthEast, _) => HexCoord::at(one(), -one()),
+            (SouthWest, _) => HexCoord::at(-one(), one()),
+            (East, Pointy) | (SouthEast, Flat) => HexCoord::at(one(), zero()),
+            (West, Pointy) | (NorthWest, Flat) => HexCoord::at(-one(), zero()),
+            (SouthEast, Pointy) | (South, Flat) => HexCoord::at(zero(), one()),
+            (NorthWest, Pointy) | (North, Flat) => HexCoord::at(zero(), -one()),
+            (direction, layout) => bail!("There is no {:?} in the {:?} layout", direction, layout),
+        })
+    }
+
+    /// Parse a string of directions that are not separated, like `"esenee"`.
+    /// ```
+    /// use aoc::HexDirection;
+    /// use aoc::HexDirection::*;
+    ///
+    /// assert_eq!(HexDirection::parse_path("esenee").unwrap(), vec![East, SouthEast, NorthEast, East]);
+    /// assert!(HexDirection::parse_path("ex").is_err());
+    /// ```
+    pub fn parse_path(s: &str) -> Result<Vec<Self>> {
+        let mut ret = Vec::new();
+        let mut s = s.trim();
+
+        while !s.is_empty() {
+            let len = if s.starts_with('n') || s.starts_with('s') {
+                match s.as_bytes().get(1) {
+                    Some(b'e' | b'w') => 2,
+                    _ => 1,
+                }
+            } else {
+                1
+            };
+            let (direction, rest) = s.split_at(len);
+            ret.push(direction.parse()?);
+            s = rest;
+        }
+
+        Ok(ret)
+    }
+}
+
+impl FromStr for HexDirection {
+    type Err = Error;
+
+    /// Generate an `HexDirection` from a string. Both the short and long form are accepted:
+    /// - `North`: "n" | "north"
+    /// - `NorthEast`: "ne" | "northeast" | "north-east"
+    /// - `East`: "e" | "east"
+    /// - `SouthEast`: "se" | "southeast" | "south-east"
+    /// - `South`: "s" | "south"
+    /// - `SouthWest`: "sw" | "southwest" | "south-west"
+    /// - `West`: "w" | "west"
+    /// - `NorthWest`: "nw" | "northwest" | "north-west"
+    /// ```
+    /// use aoc::HexDirection;
+    ///
+    /// assert_eq!("ne".parse::<HexDirection>().unwrap(), HexDirection::NorthEast);
+    /// assert_eq!("W".parse::<HexDirection>().unwrap(), HexDirection::West);
+    /// assert_eq!(" south-east ".parse::<HexDirection>().unwrap(), HexDirection::SouthEast);
+    /// ```
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s.to_lowercase().trim() {
+            "n" | "north" => Self::North,
+            "ne" | "northeast" | "north-east" => Self::NorthEast,
+            "e" | "east" => Self::East,
+            "se" | "southeast" | "south-east" => Self::SouthEast,
+            "s" | "south" => Self::South,
+            "sw" | "southwest" | "south-west" => Self::SouthWest,
+            "w" | "west" => Self::West,
+            "nw" | "northwest" | "north-west" => Self::NorthWest,
+            s => bail!("can’t convert {} as an hexagonal direction", s),
+        })
+    }
+}
+
+/// A sparse hexagonal map associating values to [HexCoord]s.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HexMap<T> {
+    pub layout: HexLayout,
+    cells: HashMap<HexCoord<isize>, T>,
+}
+
+impl<T> HexMap<T> {
+    /// Create an empty [HexMap] drawn in the specified layout.
+    pub fn new(layout: HexLayout) -> Self {
+        Self {
+            layout,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Insert a value in the map, returns the previous value if there was one.
+    pub fn insert(&mut self, coord: HexCoord<isize>, value: T) -> Option<T> {
+        self.cells.insert(coord, value)
+    }
+
+    /// Remove a value from the map.
+    pub fn remove(&mut self, coord: &HexCoord<isize>) -> Option<T> {
+        self.cells.remove(coord)
+    }
+
+    pub fn get(&self, coord: &HexCoord<isize>) -> Option<&T> {
+        self.cells.get(coord)
+    }
+
+    pub fn get_mut(&mut self, coord: &HexCoord<isize>) -> Option<&mut T> {
+        self.cells.get_mut(coord)
+    }
+
+    pub fn contains(&self, coord: &HexCoord<isize>) -> bool {
+        self.cells.contains_key(coord)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Iterate over all the coordinates and values of the map with no defined order.
+    pub fn iter(&self) -> impl Iterator<Item = (&HexCoord<isize>, &T)> {
+        self.cells.iter()
+    }
+
+    /// Iterate over all the coordinates and values of the map with no defined order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&HexCoord<isize>, &mut T)> {
+        self.cells.iter_mut()
+    }
+
+    /// Returns the number of neighbors of a coordinate that are present in the map.
+    /// ```
+    /// use aoc::{HexCoord, HexLayout, HexMap};
+    ///
+    /// let mut map = HexMap::new(HexLayout::Pointy);
+    /// map.insert(HexCoord::at(1, 0), ());
+    /// map.insert(HexCoord::at(0, 1), ());
+    /// map.insert(HexCoord::at(2, 2), ());
+    ///
+    /// assert_eq!(map.count_neighbors(&HexCoord::at(0, 0)), 2);
+    /// ```
+    pub fn count_neighbors(&self, coord: &HexCoord<isize>) -> usize {
+        coord
+            .neighbors()
+            .filter(|coord| self.contains(coord))
+            .count()
+    }
+
+    /// Return the position of every cell on the drawing, in the `(column, line)` form.
+    /// The pointy layout uses a doubled width, the flat layout uses a doubled height.
+    fn drawing_position(&self, coord: &HexCoord<isize>) -> (isize, isize) {
+        match self.layout {
+            HexLayout::Pointy => (2 * coord.q + coord.r, coord.r),
+            HexLayout::Flat => (coord.q, 2 * coord.r + coord.q),
+        }
+    }
+}
+
+impl<T> FromIterator<(HexCoord<isize>, T)> for HexMap<T> {
+    fn from_iter<It: IntoIterator<Item = (HexCoord<isize>, T)>>(iter: It) -> Self {
+        Self {
+            layout: HexLayout::default(),
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T: Display> Display for HexMap<T> {
+    /// Draw the map in its layout, the empty hexagons are drawn with a `.`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let positions: HashMap<_, _> = self
+            .cells
+            .iter()
+            .map(|(coord, value)| (self.drawing_position(coord), value))
+            .collect();
+        let width = self
+            .cells
+            .values()
+            .map(|value| value.to_string().chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let min_x = positions.keys().map(|(x, _)| *x).min().unwrap();
+        let max_x = positions.keys().map(|(x, _)| *x).max().unwrap();
+        let min_y = positions.keys().map(|(_, y)| *y).min().unwrap();
+        let max_y = positions.keys().map(|(_, y)| *y).max().unwrap();
+
+        for y in min_y..=max_y {
+            let mut line = String::new();
+            for x in min_x..=max_x {
+                let cell = if let Some(value) = positions.get(&(x, y)) {
+                    value.to_string()
+                } else if (x + y) % 2 == 0 {
+                    String::from(".")
+                } else {
+                    String::new()
+                };
+                line.push_str(&format!("{:>1$}", cell, width));
+                if self.layout == HexLayout::Flat {
+                    line.push(' ');
+                }
+            }
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation() {
+        for coord in [
+            HexCoord::at(3, -1),
+            HexCoord::at(0, 4),
+            HexCoord::at(-2, -2),
+        ] {
+            for n in 0..6 {
+                let rotated = coord.rotate_clockwise_n(n);
+                assert_eq!(rotated.distance_from_base(), coord.distance_from_base());
+                assert_eq!(rotated.rotate_counter_clockwise_n(n), coord);
+            }
+        }
+    }
+
+    #[test]
+    fn ring() {
+        let center = HexCoord::at(2, -3);
+        for distance in 0..5 {
+            let ring = center.ring(distance);
+            assert!(ring.iter().all(|c| c.distance_from(&center) == distance));
+            assert_eq!(ring.len(), (distance as usize * 6).max(1));
+        }
+    }
+
+    #[test]
+    fn layouts_agree_on_neighbors() {
+        let origin = HexCoord::<isize>::default();
+        for layout in [HexLayout::Pointy, HexLayout::Flat] {
+            let mut neighbors: Vec<_> = layout
+                .directions()
+                .iter()
+                .map(|dir| origin.step(*dir, layout).unwrap())
+                .collect();
+            neighbors.sort_by_key(|c| (c.q, c.r));
+            let mut expected: Vec<_> = origin.neighbors().collect();
+            expected.sort_by_key(|c| (c.q, c.r));
+            assert_eq!(neighbors, expected);
+        }
+    }
+
+    #[test]
+    fn follow_path() {
+        // 2017 day 11
+        let end = "se,sw,se,sw,sw"
+            .split(',')
+            .map(|dir| dir.parse::<HexDirection>().unwrap())
+            .fold(HexCoord::<isize>::default(), |coord, dir| {
+                coord.step(dir, HexLayout::Flat).unwrap()
+            });
+        assert_eq!(end.distance_from_base(), 3);
+
+        // 2020 day 24
+        let end = HexDirection::parse_path("nwwswee")
+            .unwrap()
+            .into_iter()
+            .fold(HexCoord::<isize>::default(), |coord, dir| {
+                coord.step(dir, HexLayout::Pointy).unwrap()
+            });
+        assert_eq!(end, HexCoord::default());
+    }
+
+    #[test]
+    fn display() {
+        let map = HexMap::<char>::new(HexLayout::Pointy);
+        insta::assert_display_snapshot!(map, @"");
+
+        let mut map: HexMap<char> = HexCoord::at(0, 0)
+            .neighbors()
+            .map(|coord| (coord, '#'))
+            .collect();
+        map.insert(HexCoord::at(0, 0), 'o');
+        insta::assert_display_snapshot!(map, @r###"
+         # #
+        # o #
+         # #
+        "###);
+
+        map.layout = HexLayout::Flat;
+        insta::assert_display_snapshot!(map, @r###"
+          #
+        #   #
+          o
+        #   #
+          #
+        "###);
+
+        let map: HexMap<char> = [(HexCoord::at(0, 0), 'a'), (HexCoord::at(2, 1), 'b')]
+            .into_iter()
+            .collect();
+        insta::assert_display_snapshot!(map, @r###"
+        a . .
+         . . b
+        "###);
+    }
+}
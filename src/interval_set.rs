@@ -0,0 +1,330 @@
+//! Define an [IntervalSet], a set of disjoint integer intervals.
+
+use crate::num;
+use std::ops::{self, RangeInclusive};
+
+/// A set of numbers stored as a sorted list of disjoint inclusive intervals.
+/// Overlapping or adjacent intervals are merged as soon as they're inserted.
+/// ```
+/// use aoc::IntervalSet;
+///
+/// let mut set: IntervalSet<i64> = [0..=3, 10..=12].into_iter().collect();
+/// set.insert(4..=5);
+/// set.remove(11..=11);
+///
+/// assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..=5, 10..=10, 12..=12]);
+/// assert_eq!(set.total_len(), 8);
+/// assert!(set.contains(4));
+/// assert!(!set.contains(11));
+/// ```
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct IntervalSet<I> {
+    intervals: Vec<(I, I)>,
+}
+
+/// The default interval set is empty.
+impl<I> Default for IntervalSet<I> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<I> IntervalSet<I>
+where
+    I: ops::Sub<Output = I>
+        + ops::Add<Output = I>
+        + num::Zero
+        + num::One
+        + num::CheckedOp
+        + num::WideOp
+        + Ord
+        + Copy,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return `true` if the set doesn't contain any number.
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Return the number of disjoint intervals in the set.
+    pub fn interval_count(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Return the number of numbers contained in the set. It can only overflow if the set
+    /// contains every `u128` or every `i128`.
+    /// ```
+    /// use aoc::IntervalSet;
+    ///
+    /// let set: IntervalSet<i8> = [i8::MIN..=i8::MAX].into_iter().collect();
+    /// assert_eq!(set.total_len(), 256);
+    /// ```
+    pub fn total_len(&self) -> u128 {
+        self.intervals
+            .iter()
+            .map(|&(start, end)| end.wide_distance(start) + 1)
+            .sum()
+    }
+
+    /// Iterate over the disjoint intervals of the set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<I>> + '_ {
+        self.intervals.iter().map(|&(start, end)| start..=end)
+    }
+
+    /// Return `true` if `value` is in one of the intervals.
+    pub fn contains(&self, value: I) -> bool {
+        let idx = self.intervals.partition_point(|&(_, end)| end < value);
+        self.intervals
+            .get(idx)
+            .is_some_and(|&(start, _)| start <= value)
+    }
+
+    /// Add all the numbers of `range` to the set, merging it with the overlapping and adjacent
+    /// intervals.
+    /// ```
+    /// use aoc::IntervalSet;
+    ///
+    /// let mut set = IntervalSet::new();
+    /// set.insert(0..=2);
+    /// set.insert(6..=8_u8);
+    /// assert_eq!(set.interval_count(), 2);
+    ///
+    /// set.insert(3..=5);
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..=8]);
+    /// ```
+    pub fn insert(&mut self, range: RangeInclusive<I>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let first = self.intervals.partition_point(|&(_, e)| {
+            e.checked_add(I::one())
+                .is_some_and(|after_end| after_end < start)
+        });
+        let last = self.intervals.partition_point(|&(s, _)| {
+            end.checked_add(I::one())
+                .is_none_or(|after_end| s <= after_end)
+        });
+        if first < last {
+            start = start.min(self.intervals[first].0);
+            end = end.max(self.intervals[last - 1].1);
+        }
+        self.intervals.splice(first..last, [(start, end)]);
+    }
+
+    /// Remove all the numbers of `range` from the set, splitting the intervals if needed.
+    /// ```
+    /// use aoc::IntervalSet;
+    ///
+    /// let mut set: IntervalSet<isize> = [0..=10].into_iter().collect();
+    /// set.remove(3..=4);
+    /// set.remove(10..=20);
+    ///
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..=2, 5..=9]);
+    /// ```
+    pub fn remove(&mut self, range: RangeInclusive<I>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let first = self.intervals.partition_point(|&(_, e)| e < start);
+        let last = self.intervals.partition_point(|&(s, _)| s <= end);
+        if first >= last {
+            return;
+        }
+
+        let mut remaining = Vec::with_capacity(2);
+        let (first_start, _) = self.intervals[first];
+        let (_, last_end) = self.intervals[last - 1];
+        if first_start < start {
+            remaining.push((first_start, start - I::one()));
+        }
+        if last_end > end {
+            remaining.push((end + I::one(), last_end));
+        }
+        self.intervals.splice(first..last, remaining);
+    }
+
+    /// Return all the numbers contained in both sets.
+    /// ```
+    /// use aoc::IntervalSet;
+    ///
+    /// let left: IntervalSet<i32> = [0..=5, 10..=15].into_iter().collect();
+    /// let right: IntervalSet<i32> = [3..=12].into_iter().collect();
+    ///
+    /// assert_eq!(left.intersection(&right).iter().collect::<Vec<_>>(), vec![3..=5, 10..=12]);
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut left, mut right) = (self.intervals.iter(), other.intervals.iter());
+        let (mut l, mut r) = (left.next(), right.next());
+
+        while let (Some(&(l_start, l_end)), Some(&(r_start, r_end))) = (l, r) {
+            let (start, end) = (l_start.max(r_start), l_end.min(r_end));
+            if start <= end {
+                intervals.push((start, end));
+            }
+            if l_end < r_end {
+                l = left.next();
+            } else {
+                r = right.next();
+            }
+        }
+        Self { intervals }
+    }
+
+    /// Return all the numbers contained in at least one of the sets.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ret = self.clone();
+        other.iter().for_each(|range| ret.insert(range));
+        ret
+    }
+
+    /// Return all the numbers within `bounds` that are not in the set.
+    /// ```
+    /// use aoc::IntervalSet;
+    ///
+    /// let set: IntervalSet<u32> = [0..=5, 10..=15].into_iter().collect();
+    ///
+    /// assert_eq!(set.complement(0..=20).iter().collect::<Vec<_>>(), vec![6..=9, 16..=20]);
+    /// ```
+    pub fn complement(&self, bounds: RangeInclusive<I>) -> Self {
+        let mut ret: Self = [bounds].into_iter().collect();
+        self.iter().for_each(|range| ret.remove(range));
+        ret
+    }
+
+    /// Move the numbers through a piecewise function. Each piece is an interval and the signed
+    /// offset to add to the numbers it contains. The numbers covered by several pieces are only
+    /// moved by the first one, and the numbers not covered by any piece are not moved.
+    ///
+    /// # Panics
+    /// If a moved number doesn't fit in `I`.
+    /// ```
+    /// use aoc::IntervalSet;
+    ///
+    /// let seeds: IntervalSet<u64> = [79..=92, 55..=67].into_iter().collect();
+    /// let soil = seeds.map_offsets([(98..=99, -48), (50..=97, 2)]);
+    ///
+    /// assert_eq!(soil.iter().collect::<Vec<_>>(), vec![57..=69, 81..=94]);
+    ///
+    /// let moved = seeds.map_offsets([(0..=60, 100)]);
+    /// assert_eq!(moved.iter().collect::<Vec<_>>(), vec![61..=67, 79..=92, 155..=160]);
+    /// ```
+    pub fn map_offsets(&self, pieces: impl IntoIterator<Item = (RangeInclusive<I>, i128)>) -> Self {
+        let mut remaining = self.clone();
+        let mut ret = Self::new();
+
+        for (source, offset) in pieces {
+            let source: Self = [source].into_iter().collect();
+            let moved = |n: I| {
+                n.checked_offset(offset)
+                    .expect("The moved numbers don't fit in the type")
+            };
+            for range in remaining.intersection(&source).iter() {
+                ret.insert(moved(*range.start())..=moved(*range.end()));
+            }
+            source.iter().for_each(|range| remaining.remove(range));
+        }
+        remaining.iter().for_each(|range| ret.insert(range));
+        ret
+    }
+}
+
+impl<I> FromIterator<RangeInclusive<I>> for IntervalSet<I>
+where
+    I: ops::Sub<Output = I>
+        + ops::Add<Output = I>
+        + num::Zero
+        + num::One
+        + num::CheckedOp
+        + num::WideOp
+        + Ord
+        + Copy,
+{
+    fn from_iter<T: IntoIterator<Item = RangeInclusive<I>>>(iter: T) -> Self {
+        let mut ret = Self::new();
+        iter.into_iter().for_each(|range| ret.insert(range));
+        ret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn assert_same(set: &IntervalSet<u8>, expected: &BTreeSet<u8>) {
+        let numbers: BTreeSet<u8> = set.iter().flatten().collect();
+        assert_eq!(&numbers, expected);
+        assert_eq!(set.total_len() as usize, expected.len());
+        for window in set.intervals.windows(2) {
+            assert!(window[0].1 + 1 < window[1].0, "{set:?} is not merged");
+        }
+    }
+
+    #[test]
+    fn matches_btreeset() {
+        let mut set = IntervalSet::new();
+        let mut expected = BTreeSet::new();
+        let mut seed = 7_u32;
+        let mut next = move || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (seed >> 16) as u8 % 64
+        };
+
+        for _ in 0..500 {
+            let (a, b) = (next(), next());
+            let range = a.min(b)..=a.max(b).min(a.min(b) + 8);
+            if next() % 3 == 0 {
+                set.remove(range.clone());
+                range.for_each(|n| {
+                    expected.remove(&n);
+                });
+            } else {
+                set.insert(range.clone());
+                expected.extend(range);
+            }
+            assert_same(&set, &expected);
+        }
+
+        let complement = set.complement(10..=50);
+        let expected_complement: BTreeSet<u8> =
+            (10..=50).filter(|n| !expected.contains(n)).collect();
+        assert_same(&complement, &expected_complement);
+        assert!(set.intersection(&complement).is_empty());
+        assert_same(
+            &set.union(&complement),
+            &expected.union(&expected_complement).copied().collect(),
+        );
+    }
+
+    #[test]
+    fn bounds_of_the_type() {
+        let mut set: IntervalSet<u8> = [0..=10, 250..=255].into_iter().collect();
+        set.insert(11..=249);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..=255]);
+
+        set.remove(0..=0);
+        set.remove(255..=255);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..=254]);
+        assert_eq!(set.complement(0..=255).total_len(), 2);
+        assert_eq!(set.map_offsets([(1..=254, 1)]).total_len(), 254);
+        assert_eq!(
+            set.map_offsets([(1..=9, -1), (200..=254, 1)])
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![0..=8, 10..=199, 201..=255]
+        );
+
+        let full: IntervalSet<i8> = [i8::MIN..=i8::MAX].into_iter().collect();
+        assert_eq!(full.total_len(), 256);
+        let full: IntervalSet<u64> = [0..=u64::MAX].into_iter().collect();
+        assert_eq!(full.total_len(), u64::MAX as u128 + 1);
+    }
+}
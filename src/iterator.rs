@@ -1,3 +1,5 @@
+use crate::{BoundingBox, Coord};
+
 pub trait Until<T> {
     fn until(&mut self, check: impl Fn(&T) -> bool) -> usize;
 }
@@ -18,3 +20,29 @@ where
         cpt
     }
 }
+
+/// Compute the [BoundingBox] of an [Iterator] of [Coord]s.
+pub trait BoundingBoxExt<I> {
+    /// Compute the [BoundingBox] of all the coordinates in one pass.
+    /// Returns `None` if the iterator was empty.
+    /// ```
+    /// use aoc::Coord;
+    /// use aoc::iterator::BoundingBoxExt;
+    ///
+    /// let bbox = [Coord::at(1.5, 2.), Coord::at(-1., 0.5)].into_iter().bounding_box().unwrap();
+    ///
+    /// assert_eq!(bbox.min, Coord::at(-1., 0.5));
+    /// assert_eq!(bbox.max, Coord::at(1.5, 2.));
+    /// ```
+    fn bounding_box(self) -> Option<BoundingBox<I>>;
+}
+
+impl<T, I> BoundingBoxExt<I> for T
+where
+    T: Iterator<Item = Coord<I>>,
+    I: PartialOrd + Copy,
+{
+    fn bounding_box(self) -> Option<BoundingBox<I>> {
+        BoundingBox::from_coords(self)
+    }
+}
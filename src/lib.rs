@@ -1,28 +1,46 @@
+mod bounding_box;
 mod coord;
+mod cuboid;
 mod cyclic_list;
 mod direction;
 mod graph;
 mod grid;
+mod hex;
+mod interval_set;
 pub mod iterator;
 pub mod macros;
+mod manhattan;
 mod movement;
 pub mod num;
 pub mod parser;
+mod path;
 mod range;
+mod rect;
+mod rotation;
 mod shape;
 mod space;
 mod turtle;
+mod walker;
 
+pub use bounding_box::BoundingBox;
 pub use coord::Coord;
+pub use cuboid::{Cuboid, CuboidSet};
 pub use cyclic_list::CyclicList;
-pub use direction::Direction;
-pub use graph::{Directed, Graph, Id, Undirected};
+pub use direction::{Direction, Direction8, Turn};
+pub use graph::{DfsEvent, Directed, Export, Graph, GraphKind, Id, MinCut, NodeStyle, Undirected};
 pub use grid::Grid;
-pub use movement::Movement;
+pub use hex::{HexCoord, HexDirection, HexLayout, HexMap};
+pub use interval_set::IntervalSet;
+pub use manhattan::ManhattanBall;
+pub use movement::{Movement, MovementParser};
+pub use path::Path;
 pub use range::Range;
+pub use rect::Rect;
+pub use rotation::Rotation;
 pub use shape::Shape;
 pub use space::Space;
-pub use turtle::Turtle;
+pub use turtle::{Turtle, TurtleMode};
+pub use walker::{EdgeRule, Walker};
 
 pub use anyhow::*;
 pub use atty;
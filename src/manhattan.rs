@@ -0,0 +1,241 @@
+//! Define a [ManhattanBall], all the coordinates within a manhattan distance of a center.
+//!
+//! Turning the space by 45 degrees transforms a manhattan ball into a square, a lot of operations
+//! are way easier in this rotated space, see [Coord::to_rotated_axes].
+
+use crate::{Coord, Rect};
+use std::ops::RangeInclusive;
+
+/// All the coordinates at a manhattan distance lower or equal to `radius` from the `center`.
+///
+/// If the `center` is `S` and the radius is 2, the ball contains all the `#` coordinates.
+/// ```text
+/// . . . . . . .
+/// . . . # . . .
+/// . . # # # . .
+/// . # # S # # .
+/// . . # # # . .
+/// . . . # . . .
+/// . . . . . . .
+/// ```
+#[derive(Debug, Default, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct ManhattanBall {
+    pub center: Coord<isize>,
+    pub radius: isize,
+}
+
+impl ManhattanBall {
+    pub fn new(center: Coord<isize>, radius: isize) -> Self {
+        Self { center, radius }
+    }
+
+    /// Create the smallest ball centered on `center` containing `point`.
+    /// ```
+    /// use aoc::{Coord, ManhattanBall};
+    ///
+    /// let ball = ManhattanBall::reaching(Coord::at(8, 7), Coord::at(2, 10));
+    /// assert_eq!(ball.radius, 9);
+    /// ```
+    pub fn reaching(center: Coord<isize>, point: Coord<isize>) -> Self {
+        Self::new(center, center.manhattan_distance_from(&point))
+    }
+
+    /// Return `true` if the coordinate is in the ball.
+    /// ```
+    /// use aoc::{Coord, ManhattanBall};
+    ///
+    /// let ball = ManhattanBall::new(Coord::at(0, 0), 2);
+    ///
+    /// assert!(ball.contains(Coord::at(1, -1)));
+    /// assert!(ball.contains(Coord::at(0, 2)));
+    /// assert!(!ball.contains(Coord::at(2, 1)));
+    /// ```
+    pub fn contains(&self, coord: Coord<isize>) -> bool {
+        self.center.manhattan_distance_from(&coord) <= self.radius
+    }
+
+    /// Return the number of coordinates in the ball.
+    /// ```
+    /// use aoc::{Coord, ManhattanBall};
+    ///
+    /// assert_eq!(ManhattanBall::new(Coord::at(0, 0), 0).len(), 1);
+    /// assert_eq!(ManhattanBall::new(Coord::at(0, 0), 2).len(), 13);
+    /// assert_eq!(ManhattanBall::new(Coord::at(0, 0), -1).len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        if self.radius < 0 {
+            0
+        } else {
+            (2 * self.radius * self.radius + 2 * self.radius + 1) as usize
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.radius < 0
+    }
+
+    /// Return the coordinates on the `x` axis covered by the ball on the line `y`.
+    /// ```
+    /// use aoc::{Coord, ManhattanBall};
+    ///
+    /// let ball = ManhattanBall::new(Coord::at(8, 7), 9);
+    ///
+    /// assert_eq!(ball.span_at_y(7), Some(-1..=17));
+    /// assert_eq!(ball.span_at_y(10), Some(2..=14));
+    /// assert_eq!(ball.span_at_y(16), Some(8..=8));
+    /// assert_eq!(ball.span_at_y(17), None);
+    /// ```
+    pub fn span_at_y(&self, y: isize) -> Option<RangeInclusive<isize>> {
+        let half_width = self.radius - (y - self.center.y).abs();
+        (half_width >= 0).then(|| self.center.x - half_width..=self.center.x + half_width)
+    }
+
+    /// Return the coordinates on the `y` axis covered by the ball on the column `x`.
+    pub fn span_at_x(&self, x: isize) -> Option<RangeInclusive<isize>> {
+        let half_height = self.radius - (x - self.center.x).abs();
+        (half_height >= 0).then(|| self.center.y - half_height..=self.center.y + half_height)
+    }
+
+    /// Lazily iterate over all the coordinates of the ball, line by line.
+    /// ```
+    /// use aoc::{Coord, ManhattanBall};
+    ///
+    /// let mut coords = ManhattanBall::new(Coord::at(0, 0), 1).iter();
+    ///
+    /// assert_eq!(coords.next(), Some(Coord::at(0, -1)));
+    /// assert_eq!(coords.next(), Some(Coord::at(-1, 0)));
+    /// assert_eq!(coords.next(), Some(Coord::at(0, 0)));
+    /// assert_eq!(coords.next(), Some(Coord::at(1, 0)));
+    /// assert_eq!(coords.next(), Some(Coord::at(0, 1)));
+    /// assert_eq!(coords.next(), None);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = Coord<isize>> {
+        let ball = *self;
+        (ball.center.y - ball.radius..=ball.center.y + ball.radius).flat_map(move |y| {
+            ball.span_at_y(y)
+                .into_iter()
+                .flatten()
+                .map(move |x| Coord::at(x, y))
+        })
+    }
+
+    /// Lazily iterate over the border of the ball, see [Coord::manhattan_ring].
+    pub fn border(&self) -> impl Iterator<Item = Coord<isize>> {
+        self.center.manhattan_ring(self.radius)
+    }
+
+    /// Lazily iterate over the coordinates just outside of the ball.
+    /// When looking for the only coordinate not covered by a bunch of balls it's the only place
+    /// worth looking at.
+    pub fn outer_border(&self) -> impl Iterator<Item = Coord<isize>> {
+        self.center.manhattan_ring(self.radius + 1)
+    }
+
+    /// Return `true` if the two balls share at least one coordinate, an empty ball shares none.
+    /// ```
+    /// use aoc::{Coord, ManhattanBall};
+    ///
+    /// let ball = ManhattanBall::new(Coord::at(0, 0), 2);
+    ///
+    /// assert!(ball.intersects(&ManhattanBall::new(Coord::at(3, 1), 2)));
+    /// assert!(!ball.intersects(&ManhattanBall::new(Coord::at(3, 2), 2)));
+    /// assert!(!ball.intersects(&ManhattanBall::new(Coord::at(0, 0), -1)));
+    /// ```
+    pub fn intersects(&self, other: &Self) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && self.center.manhattan_distance_from(&other.center) <= self.radius + other.radius
+    }
+
+    /// Return the square containing the ball in the rotated space, see [Coord::to_rotated_axes].
+    /// Returns `None` if the ball is empty.
+    /// ```
+    /// use aoc::{Coord, ManhattanBall};
+    ///
+    /// let square = ManhattanBall::new(Coord::at(3, 1), 2).to_rotated_rect().unwrap();
+    ///
+    /// assert_eq!(*square.start(), Coord::at(2, 0));
+    /// assert_eq!(*square.end(), Coord::at(6, 4));
+    /// assert_eq!(ManhattanBall::new(Coord::at(3, 1), -1).to_rotated_rect(), None);
+    /// ```
+    pub fn to_rotated_rect(&self) -> Option<Rect<isize>> {
+        let center = self.center.to_rotated_axes();
+        Rect::new(center - self.radius, center + self.radius).ok()
+    }
+
+    /// Return the intersection of two balls in the rotated space, see [Coord::to_rotated_axes].
+    /// Only the coordinates of the resulting rectangle with an even `x + y` can be converted
+    /// back with [Coord::from_rotated_axes]. Returns `None` if the balls don't intersect or one
+    /// of them is empty.
+    /// ```
+    /// use aoc::{Coord, ManhattanBall};
+    ///
+    /// let ball = ManhattanBall::new(Coord::at(0, 0), 2);
+    /// let other = ManhattanBall::new(Coord::at(3, 1), 2);
+    ///
+    /// let intersection = ball.intersection(&other).unwrap();
+    /// let coords: Vec<_> = intersection.iter().filter_map(Coord::from_rotated_axes).collect();
+    /// assert_eq!(coords, vec![Coord::at(1, 1), Coord::at(2, 0)]);
+    ///
+    /// assert!(ball.intersection(&ManhattanBall::new(Coord::at(3, 2), 2)).is_none());
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Option<Rect<isize>> {
+        self.to_rotated_rect()?
+            .intersection(&other.to_rotated_rect()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iter_matches_contains() {
+        let ball = ManhattanBall::new(Coord::at(-3, 2), 4);
+        let coords: Vec<_> = ball.iter().collect();
+
+        assert_eq!(coords.len(), ball.len());
+        for coord in Coord::at(-10, -10).to(Coord::at(10, 10)).unwrap() {
+            assert_eq!(ball.contains(coord), coords.contains(&coord));
+        }
+        assert!(ball.border().all(|coord| ball.contains(coord)));
+        assert!(ball.outer_border().all(|coord| !ball.contains(coord)));
+    }
+
+    #[test]
+    fn intersection_matches_contains() {
+        let ball = ManhattanBall::new(Coord::at(0, 0), 3);
+
+        for center in Coord::at(-8, -8).to(Coord::at(8, 8)).unwrap() {
+            let other = ManhattanBall::new(center, 2);
+            let mut expected: Vec<_> = ball.iter().filter(|c| other.contains(*c)).collect();
+            expected.sort();
+
+            let mut got: Vec<_> = ball
+                .intersection(&other)
+                .into_iter()
+                .flatten()
+                .filter_map(Coord::from_rotated_axes)
+                .collect();
+            got.sort();
+
+            assert_eq!(got, expected);
+            assert_eq!(ball.intersects(&other), !expected.is_empty());
+        }
+    }
+
+    #[test]
+    fn negative_radius() {
+        let empty = ManhattanBall::new(Coord::at(0, 0), -1);
+        let ball = ManhattanBall::new(Coord::at(0, 0), 2);
+
+        assert!(empty.is_empty());
+        assert_eq!(empty.iter().count(), 0);
+        assert!(!empty.contains(Coord::at(0, 0)));
+        assert_eq!(empty.to_rotated_rect(), None);
+        assert_eq!(empty.intersection(&ball), None);
+        assert_eq!(ball.intersection(&empty), None);
+        assert!(!empty.intersects(&ball));
+        assert!(!ball.intersects(&empty));
+    }
+}
@@ -1,5 +1,7 @@
-use anyhow::{Error, Result};
+use anyhow::{anyhow, bail, Context, Error, Result};
+use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::LazyLock;
 
 /// Describe a movement in one direction with a certain length
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -82,19 +84,34 @@ impl Movement {
         }
     }
 
-    /// Generate an iterator from one movement by exploding the Movement(n) into Movement(1)
-    /// followed by n times Forward(1)
+    /// Generate an iterator from one movement by exploding the Movement(n) into movements of
+    /// length 1 following the same path when applied on a [Turtle](crate::Turtle).
+    ///
+    /// - An absolute movement (`North`, `East`, `South`, `West`) is repeated `|n|` times with a
+    ///   value of `1` or `-1`, so `North(-2)` gives `North(-1), North(-1)`.
+    /// - A turn (`Right`, `Left`) gives the turn with a value of `1` or `-1` followed by `|n| - 1`
+    ///   times `Forward(1)` or `Forward(-1)`, keeping the sign of `n`.
+    /// - `Forward(n)` is repeated `|n|` times as `Forward(1)` or `Forward(-1)`.
+    /// - A movement of `0` gives itself.
     ///
     /// ```
     /// use aoc::Movement;
     ///
     /// assert_eq!(Movement::Right(2).explode().collect::<Vec<_>>(), &[Movement::Right(1), Movement::Forward(1)]);
+    /// assert_eq!(Movement::Right(-2).explode().collect::<Vec<_>>(), &[Movement::Right(-1), Movement::Forward(-1)]);
     /// assert_eq!(Movement::Right(0).explode().collect::<Vec<_>>(), &[Movement::Right(0)]);
+    /// assert_eq!(Movement::North(2).explode().collect::<Vec<_>>(), &[Movement::North(1), Movement::North(1)]);
     /// ```
     pub fn explode(self) -> impl Iterator<Item = Self> {
-        std::iter::once(self.unit()).chain(
-            std::iter::repeat(Forward(1)).take((self.value().abs() as usize).saturating_sub(1)),
-        )
+        let unit = self.unit();
+        let rest = match unit {
+            Right(n) | Left(n) => Forward(n),
+            _ => unit,
+        };
+        std::iter::once(unit).chain(std::iter::repeat_n(
+            rest,
+            self.value().unsigned_abs().saturating_sub(1),
+        ))
     }
 
     /// Try to convert a [`Movement`](crate::Movement) to a tuple containing a [`Direction`](crate::Direction)
@@ -114,33 +131,235 @@ impl Movement {
     pub fn to_dir_val(self) -> Result<(crate::Direction, isize)> {
         Ok((self.try_into()?, self.value()))
     }
+
+    /// Apply the movements one after the other on a [Turtle](crate::Turtle) and record the
+    /// resulting [Path](crate::Path).
+    /// ```
+    /// use aoc::{Coord, Movement, MovementParser, Turtle};
+    ///
+    /// let parser = MovementParser::absolute();
+    /// let moves = "R8,U5,L5,D3".split(',').map(|m| parser.parse(m).unwrap());
+    /// let path = Movement::trace(Turtle::new(), moves);
+    ///
+    /// assert_eq!(path.vertices(), &[Coord::at(0, 0), Coord::at(8, 0), Coord::at(8, -5), Coord::at(3, -5), Coord::at(3, -2)]);
+    /// assert_eq!(path.length(), 21);
+    /// ```
+    pub fn trace(start: crate::Turtle, moves: impl IntoIterator<Item = Movement>) -> crate::Path {
+        crate::Path::new(start, moves)
+    }
+}
+
+/// Parse [Movement]s with a configurable mapping from the direction to the kind of movement.
+///
+/// A movement is a direction followed by a number, with or without whitespace in between.
+/// The direction is either a word (`"N"`, `"up"`, `"forward"`) or a single symbol (`"^"`).
+/// Everything following the number after some whitespace is ignored.
+/// The directions are case insensitive.
+///
+/// The default mapping understands:
+/// - `North`: "n" | "north" | "u" | "up" | "^" | "↑"
+/// - `East`: "e" | "east" | ">" | "→"
+/// - `South`: "s" | "south" | "d" | "down" | "v" | "↓"
+/// - `West`: "w" | "west" | "<" | "←"
+/// - `Forward`: "f" | "forward"
+/// - `Right`: "r" | "right"
+/// - `Left`: "l" | "left"
+///
+/// See [MovementParser::absolute] when `L` and `R` are absolute directions.
+/// ```
+/// use aoc::{Movement, MovementParser};
+///
+/// let parser = MovementParser::default();
+///
+/// assert_eq!(parser.parse("F10").unwrap(), Movement::Forward(10));
+/// assert_eq!(parser.parse("forward 8").unwrap(), Movement::Forward(8));
+/// assert_eq!(parser.parse("R 10 (#70c710)").unwrap(), Movement::Right(10));
+/// assert_eq!(parser.parse("↑-3").unwrap(), Movement::North(-3));
+/// assert!(parser.parse("X5").is_err());
+///
+/// let parser = parser.with("x", Movement::East);
+/// assert_eq!(parser.parse("X5").unwrap(), Movement::East(5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MovementParser {
+    mapping: HashMap<String, fn(isize) -> Movement>,
+}
+
+/// The default parser interprets `L` and `R` as turns, see [MovementParser].
+impl Default for MovementParser {
+    fn default() -> Self {
+        Self::empty()
+            .with_all(&["n", "north", "u", "up", "^", "↑"], North)
+            .with_all(&["e", "east", ">", "→"], East)
+            .with_all(&["s", "south", "d", "down", "v", "↓"], South)
+            .with_all(&["w", "west", "<", "←"], West)
+            .with_all(&["f", "forward"], Forward)
+            .with_all(&["r", "right"], Right)
+            .with_all(&["l", "left"], Left)
+    }
+}
+
+impl MovementParser {
+    /// A parser that doesn't understand any direction.
+    pub fn empty() -> Self {
+        Self {
+            mapping: HashMap::new(),
+        }
+    }
+
+    /// Same as the default parser but `"l"` and `"left"` mean `West` while `"r"` and `"right"`
+    /// mean `East`.
+    /// ```
+    /// use aoc::{Movement, MovementParser};
+    ///
+    /// let parser = MovementParser::absolute();
+    ///
+    /// assert_eq!(parser.parse("R 6").unwrap(), Movement::East(6));
+    /// assert_eq!(parser.parse("left 2").unwrap(), Movement::West(2));
+    /// assert_eq!(parser.parse("U4").unwrap(), Movement::North(4));
+    /// ```
+    pub fn absolute() -> Self {
+        Self::default()
+            .with_all(&["r", "right"], East)
+            .with_all(&["l", "left"], West)
+    }
+
+    /// Map a direction to a kind of movement, replacing the previous mapping of this direction.
+    pub fn with(mut self, direction: &str, movement: fn(isize) -> Movement) -> Self {
+        self.mapping.insert(direction.to_lowercase(), movement);
+        self
+    }
+
+    fn with_all(self, directions: &[&str], movement: fn(isize) -> Movement) -> Self {
+        directions
+            .iter()
+            .fold(self, |parser, direction| parser.with(direction, movement))
+    }
+
+    /// Parse a single movement, see [MovementParser].
+    pub fn parse(&self, s: &str) -> Result<Movement> {
+        let s = s.trim();
+        let first = s
+            .chars()
+            .next()
+            .ok_or_else(|| anyhow!("Can't parse an empty movement"))?;
+        let split = if first.is_alphabetic() {
+            s.find(|c: char| !c.is_alphabetic()).unwrap_or(s.len())
+        } else {
+            first.len_utf8()
+        };
+        let (direction, rest) = s.split_at(split);
+
+        let movement = self
+            .mapping
+            .get(&direction.to_lowercase())
+            .ok_or_else(|| anyhow!("Unknown direction {:?} in movement {:?}", direction, s))?;
+        let number = rest.split_whitespace().next().unwrap_or_default();
+        if number.is_empty() {
+            bail!("Missing the length of the movement {:?}", s);
+        }
+        let n = number
+            .parse::<isize>()
+            .with_context(|| format!("Can't parse the length of the movement {:?}", s))?;
+        Ok(movement(n))
+    }
 }
 
 impl FromStr for Movement {
     type Err = Error;
 
-    /// Generate a `Movement` from a string.
-    /// Each movement is in two part, the first part indicate an action:
-    /// - `North`: "N"
-    /// - `East`: "E"
-    /// - `South`: "S"
-    /// - `West`: "W"
-    /// - `Forward`: "F"
-    /// - `Right`: "R"
-    /// - `Left`: "L"
+    /// Generate a `Movement` from a string with the default [MovementParser].
+    /// ```
+    /// use aoc::Movement;
     ///
-    /// And then we expect a number
+    /// assert_eq!("N3".parse::<Movement>().unwrap(), Movement::North(3));
+    /// assert_eq!("D 3".parse::<Movement>().unwrap(), Movement::South(3));
+    /// assert_eq!("left 90".parse::<Movement>().unwrap(), Movement::Left(90));
+    /// assert!("N".parse::<Movement>().is_err());
+    /// assert!("".parse::<Movement>().is_err());
+    /// assert!("?3".parse::<Movement>().is_err());
+    /// ```
     fn from_str(s: &str) -> Result<Self> {
-        let (left, right) = s.split_at(1);
-        Ok(match left {
-            "N" => North(right.parse::<isize>()?),
-            "W" => West(right.parse::<isize>()?),
-            "E" => East(right.parse::<isize>()?),
-            "S" => South(right.parse::<isize>()?),
-            "F" => Forward(right.parse::<isize>()?),
-            "R" => Right(right.parse::<isize>()?),
-            "L" => Left(right.parse::<isize>()?),
-            _ => panic!("unexpected string"),
-        })
+        static PARSER: LazyLock<MovementParser> = LazyLock::new(MovementParser::default);
+        PARSER.parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_puzzle_inputs() {
+        let parser = MovementParser::absolute();
+        let moves: Vec<_> = "R 6 (#70c710)\nD 5 (#0dc571)\nL 2 (#5713f0)\nU 2 (#d2c081)"
+            .lines()
+            .map(|line| parser.parse(line).unwrap())
+            .collect();
+        assert_eq!(moves, vec![East(6), South(5), West(2), North(2)]);
+
+        let moves: Vec<Movement> = "forward 5\ndown 5\nforward 8\nup 3"
+            .lines()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        assert_eq!(moves, vec![Forward(5), South(5), Forward(8), North(3)]);
+
+        let moves: Vec<Movement> = "F10 N3 F7 R90 L270 E-2"
+            .split_whitespace()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        assert_eq!(
+            moves,
+            vec![
+                Forward(10),
+                North(3),
+                Forward(7),
+                Right(90),
+                Left(270),
+                East(-2)
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_instead_of_panics() {
+        for input in ["", "   ", "5", "N", "N five", "NN5", "🐢5", "N 5.5"] {
+            assert!(input.parse::<Movement>().is_err(), "{input:?}");
+        }
+        assert!(MovementParser::empty().parse("N5").is_err());
+    }
+
+    #[test]
+    fn explode_follows_the_same_path() {
+        use crate::Turtle;
+
+        for movement in [
+            North(3),
+            South(-2),
+            West(-1),
+            Right(3),
+            Left(-2),
+            Forward(-3),
+            East(0),
+        ] {
+            let steps: Vec<_> = movement.explode().collect();
+            assert_eq!(steps.len(), movement.value().unsigned_abs().max(1));
+            assert!(steps.iter().all(|step| step.value().abs() <= 1));
+            assert!(steps
+                .iter()
+                .all(|step| step.value().signum() == movement.value().signum()));
+
+            let start = Turtle::new();
+            let exploded = steps.into_iter().fold(start, |turtle, step| turtle + step);
+            assert_eq!(exploded, start + movement, "{movement:?}");
+        }
+        assert_eq!(
+            South(-2).explode().collect::<Vec<_>>(),
+            vec![South(-1), South(-1)]
+        );
+        assert_eq!(
+            Left(-3).explode().collect::<Vec<_>>(),
+            vec![Left(-1), Forward(-1), Forward(-1)]
+        );
     }
 }
@@ -77,6 +77,53 @@ where
     }
 }
 
+/// Integer operations computed on 128 bits, so they don't overflow the type itself.
+pub trait WideOp
+where
+    Self: Sized,
+{
+    /// Return the distance between two numbers.
+    fn wide_distance(self, other: Self) -> u128;
+    /// Add a signed offset, or return `None` if the result doesn't fit in the type.
+    fn checked_offset(self, offset: i128) -> Option<Self>;
+}
+
+macro_rules! impl_wide_op_signed {
+    ($t:ty) => {
+        impl WideOp for $t {
+            fn wide_distance(self, other: Self) -> u128 {
+                (self as i128).abs_diff(other as i128)
+            }
+            fn checked_offset(self, offset: i128) -> Option<Self> {
+                (self as i128)
+                    .checked_add(offset)
+                    .and_then(|n| Self::try_from(n).ok())
+            }
+        }
+    };
+}
+
+macro_rules! impl_wide_op_unsigned {
+    ($t:ty) => {
+        impl WideOp for $t {
+            fn wide_distance(self, other: Self) -> u128 {
+                (self as u128).abs_diff(other as u128)
+            }
+            fn checked_offset(self, offset: i128) -> Option<Self> {
+                let distance = Self::try_from(offset.unsigned_abs()).ok()?;
+                if offset < 0 {
+                    self.checked_sub(distance)
+                } else {
+                    self.checked_add(distance)
+                }
+            }
+        }
+    };
+}
+
+crate::impl_for_primitive!(impl_wide_op_signed: signed);
+crate::impl_for_primitive!(impl_wide_op_unsigned: unsigned);
+
 pub trait Pattern {
     type N;
 
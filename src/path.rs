@@ -0,0 +1,196 @@
+//! Define a [Path], the trace left by a [Turtle] applying a sequence of [Movement]s.
+
+use crate::{Coord, Movement, Turtle};
+use anyhow::{ensure, Result};
+use itertools::Itertools;
+use std::collections::HashSet;
+
+/// The trace of a [Turtle] applying a sequence of [Movement]s, see [Movement::trace].
+/// Both the absolute (`North`, `East`, ...) and relative (`Left`, `Right`, `Forward`) movements
+/// are supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    start: Turtle,
+    moves: Vec<Movement>,
+    vertices: Vec<Coord<isize>>,
+}
+
+impl Path {
+    pub(crate) fn new(start: Turtle, moves: impl IntoIterator<Item = Movement>) -> Self {
+        let moves: Vec<_> = moves.into_iter().collect();
+        let vertices = std::iter::once(start.coord)
+            .chain(moves.iter().scan(start, |turtle, movement| {
+                *turtle += *movement;
+                Some(turtle.coord)
+            }))
+            .collect();
+        Self {
+            start,
+            moves,
+            vertices,
+        }
+    }
+
+    /// The position of the turtle before and after every movement, starting with the start.
+    pub fn vertices(&self) -> &[Coord<isize>] {
+        &self.vertices
+    }
+
+    /// The state of the turtle after applying all the movements.
+    pub fn end(&self) -> Turtle {
+        self.moves
+            .iter()
+            .fold(self.start, |turtle, movement| turtle + *movement)
+    }
+
+    /// Iterate over every coordinate visited by the turtle, one step at a time, see
+    /// [Turtle::steps]. The coordinates visited multiple times are returned multiple times.
+    /// ```
+    /// use aoc::{Coord, Movement, Turtle};
+    ///
+    /// let path = Movement::trace(Turtle::new(), [Movement::East(2), Movement::Right(0), Movement::Right(1)]);
+    /// let points: Vec<_> = path.points().collect();
+    ///
+    /// assert_eq!(points, vec![Coord::at(0, 0), Coord::at(1, 0), Coord::at(2, 0), Coord::at(2, 1)]);
+    /// ```
+    pub fn points(&self) -> impl Iterator<Item = Coord<isize>> + '_ {
+        std::iter::once(self.start.coord).chain(
+            self.moves
+                .iter()
+                .flat_map(|movement| self.start.steps(*movement))
+                .scan(self.start, |turtle, movement| {
+                    let previous = turtle.coord;
+                    *turtle += movement;
+                    Some((turtle.coord != previous).then_some(turtle.coord))
+                })
+                .flatten(),
+        )
+    }
+
+    /// The number of steps made by the turtle.
+    pub fn length(&self) -> isize {
+        self.vertices
+            .iter()
+            .tuple_windows()
+            .map(|(a, b)| a.manhattan_distance_from(b))
+            .sum()
+    }
+
+    /// Return the coordinates visited more than once, in the order they were visited again.
+    /// Coming back to the start at the very end of the path doesn't count.
+    /// ```
+    /// use aoc::{Coord, Movement, MovementParser, Turtle};
+    ///
+    /// let parser = MovementParser::absolute();
+    /// let moves = "U2 R1 D1 L2 R1 D1".split(' ').map(|m| parser.parse(m).unwrap());
+    /// let path = Movement::trace(Turtle::new(), moves);
+    ///
+    /// assert_eq!(path.self_intersections(), vec![Coord::at(0, -1)]);
+    /// ```
+    pub fn self_intersections(&self) -> Vec<Coord<isize>> {
+        let mut points: Vec<_> = self.points().collect();
+        if points.len() > 1 && points.last() == points.first() {
+            points.pop();
+        }
+
+        let mut seen = HashSet::new();
+        let mut reported = HashSet::new();
+        points
+            .into_iter()
+            .filter(|point| !seen.insert(*point) && reported.insert(*point))
+            .collect()
+    }
+
+    /// Return the number of coordinates enclosed by the path, including the path itself.
+    /// The area is computed from the vertices with the shoelace formula, then the Pick's theorem
+    /// gives the number of coordinates strictly inside the path.
+    ///
+    /// Returns an error if the path doesn't come back to its start.
+    /// ```
+    /// use aoc::{Coord, Movement, MovementParser, Turtle};
+    ///
+    /// let parser = MovementParser::absolute();
+    /// let moves = "R 6,D 5,L 2,D 2,R 2,D 2,L 5,U 2,L 1,U 2,R 2,U 3,L 2,U 2"
+    ///     .split(',')
+    ///     .map(|m| parser.parse(m).unwrap());
+    /// let path = Movement::trace(Turtle::new(), moves);
+    ///
+    /// assert_eq!(path.enclosed_area().unwrap(), 62);
+    /// assert_eq!(path.interior_points().unwrap(), 24);
+    /// ```
+    pub fn enclosed_area(&self) -> Result<isize> {
+        Ok(self.interior_points()? + self.length())
+    }
+
+    /// Return the number of coordinates strictly inside the path, see [Path::enclosed_area].
+    pub fn interior_points(&self) -> Result<isize> {
+        ensure!(
+            self.vertices.first() == self.vertices.last(),
+            "The path needs to come back to its start to enclose an area"
+        );
+        let double_area: isize = self
+            .vertices
+            .iter()
+            .tuple_windows()
+            .map(|(a, b)| a.x * b.y - b.x * a.y)
+            .sum();
+        // Pick's theorem: A = i + b/2 - 1
+        Ok((double_area.abs() - self.length()) / 2 + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Direction;
+
+    #[test]
+    fn relative_and_absolute_agree() {
+        let absolute = Movement::trace(
+            Turtle::new(),
+            [
+                Movement::East(3),
+                Movement::South(2),
+                Movement::West(3),
+                Movement::North(2),
+            ],
+        );
+        let relative = Movement::trace(
+            Turtle::from(Coord::at(0, 0), Direction::East),
+            [
+                Movement::Forward(3),
+                Movement::Right(2),
+                Movement::Right(3),
+                Movement::Right(2),
+            ],
+        );
+
+        assert_eq!(absolute.vertices(), relative.vertices());
+        assert!(absolute.points().eq(relative.points()));
+        assert_eq!(absolute.points().count(), 11);
+        assert_eq!(absolute.length(), 10);
+        assert_eq!(absolute.enclosed_area().unwrap(), 12);
+        assert_eq!(relative.interior_points().unwrap(), 2);
+        assert!(absolute.self_intersections().is_empty());
+        assert_eq!(relative.end().facing, Direction::North);
+    }
+
+    #[test]
+    fn negative_movements() {
+        let path = Movement::trace(
+            Turtle::new(),
+            [
+                Movement::North(-2),
+                Movement::Left(-2),
+                Movement::Forward(4),
+            ],
+        );
+
+        let points: Vec<_> = path.points().collect();
+        assert_eq!(points.len(), 9);
+        assert_eq!(points.last(), Some(&Coord::at(-2, 2)));
+        assert_eq!(path.vertices().last(), points.last());
+        assert_eq!(path.length(), 8);
+        assert!(path.enclosed_area().is_err());
+    }
+}
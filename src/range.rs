@@ -1,33 +1,204 @@
+//! Define a [Range], an [Iterator] over the coordinates of a [Rect].
+
 use crate::coord::Coord;
+use crate::num;
+use crate::rect::Rect;
 use anyhow::{ensure, Result};
 use std::fmt;
-use std::ops;
+use std::iter::FusedIterator;
+
+/// The order in which a [Range] walks through its [Rect].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Order {
+    Rows,
+    Columns,
+}
+
+/// Where a [Range] is in its walk. Computed on the first use so building a range doesn't need any
+/// arithmetic on the coordinates.
+///
+/// The positions are `(major, minor)` pairs counted in steps: `(line, column)` when walking line
+/// by line and `(column, line)` when walking column by column. They are stored on 128 bits so
+/// every coordinate of a `Rect<i64>` can be walked even though they can't be counted.
+#[derive(Debug, Clone, Copy)]
+struct Cursor {
+    /// The last position of the walk.
+    last: (u128, u128),
+    /// The next positions walked from the front and from the back.
+    front: (u128, u128),
+    back: (u128, u128),
+    /// `true` once the front and the back met.
+    done: bool,
+}
+
+impl Cursor {
+    fn new(last: (u128, u128)) -> Self {
+        Self {
+            last,
+            front: (0, 0),
+            back: last,
+            done: false,
+        }
+    }
+
+    /// The number of positions walked before `(major, minor)`, or `None` if it doesn't fit in a
+    /// `u128`.
+    fn index(&self, (major, minor): (u128, u128)) -> Option<u128> {
+        major
+            .checked_mul(self.last.1.checked_add(1)?)?
+            .checked_add(minor)
+    }
+
+    /// The number of positions left, or `None` if it doesn't fit in a `u128`.
+    fn remaining(&self) -> Option<u128> {
+        if self.done {
+            return Some(0);
+        }
+        (self.index(self.back)? - self.index(self.front)?).checked_add(1)
+    }
+
+    fn next(&mut self) -> Option<(u128, u128)> {
+        if self.done {
+            return None;
+        }
+        let position = self.front;
+        if self.front == self.back {
+            self.done = true;
+        } else if self.front.1 == self.last.1 {
+            self.front = (self.front.0 + 1, 0);
+        } else {
+            self.front.1 += 1;
+        }
+        Some(position)
+    }
+
+    fn next_back(&mut self) -> Option<(u128, u128)> {
+        if self.done {
+            return None;
+        }
+        let position = self.back;
+        if self.front == self.back {
+            self.done = true;
+        } else if self.back.1 == 0 {
+            self.back = (self.back.0 - 1, self.last.1);
+        } else {
+            self.back.1 -= 1;
+        }
+        Some(position)
+    }
 
+    /// Skip the `n` next positions from the front.
+    fn skip(&mut self, n: usize) {
+        let (Some(front), Some(back)) = (self.index(self.front), self.index(self.back)) else {
+            // Too many positions to index them, walk one by one.
+            for _ in 0..n {
+                self.next();
+            }
+            return;
+        };
+        match front.checked_add(n as u128) {
+            Some(index) if index <= back => {
+                // The index fits, so does the width of a line.
+                let width = self.last.1 + 1;
+                self.front = (index / width, index % width);
+            }
+            _ => self.done = true,
+        }
+    }
+}
+
+/// Iterate over the coordinates of a [Rect], see [Rect::iter], [Rect::iter_columns] and
+/// [Range::between].
+///
+/// By default a range walks line by line from the top left corner to the bottom right corner, but
+/// it can also start from any corner, walk column by column and skip coordinates.
+/// It can be consumed from both ends and knows how many coordinates are left, unless there are
+/// more than [usize::MAX] of them: [ExactSizeIterator::len] then saturates to [usize::MAX].
+///
+/// Iterating needs [num::WideOp], implemented by the primitive integers, to walk the coordinates
+/// on 128 bits without overflowing the type.
+/// ```
+/// use aoc::{Coord, Range};
+///
+/// let mut range = Range::between(Coord::at(2, 1), Coord::at(0, 0)).column_major();
+///
+/// assert_eq!(range.len(), 6);
+/// assert_eq!(range.next(), Some(Coord::at(2, 1)));
+/// assert_eq!(range.next(), Some(Coord::at(2, 0)));
+/// assert_eq!(range.next_back(), Some(Coord::at(0, 0)));
+/// assert_eq!(range.next_back(), Some(Coord::at(0, 1)));
+/// assert_eq!(range.len(), 2);
+/// assert_eq!(*range.rect().start(), Coord::at(0, 0));
+/// ```
 #[derive(Debug, Clone)]
 pub struct Range<I = usize> {
-    pub start: Coord<I>,
-    current: Coord<I>,
-    finished: bool,
-    pub end: Coord<I>,
+    rect: Rect<I>,
+    from: Coord<I>,
+    to: Coord<I>,
+    /// `None` walks every coordinate.
+    step: Option<Coord<I>>,
+    order: Order,
+    cursor: Option<Cursor>,
+}
+
+impl<I> Range<I> {
+    /// The rectangle we're iterating over.
+    pub fn rect(&self) -> &Rect<I> {
+        &self.rect
+    }
+
+    pub(crate) fn walk(rect: Rect<I>, from: Coord<I>, to: Coord<I>) -> Self {
+        Self {
+            rect,
+            from,
+            to,
+            step: None,
+            order: Order::Rows,
+            cursor: None,
+        }
+    }
+
+    /// Walk the rectangle column by column instead of line by line.
+    /// The iteration restarts from the beginning.
+    pub fn column_major(mut self) -> Self {
+        self.order = Order::Columns;
+        self.cursor = None;
+        self
+    }
 }
 
 impl<I: fmt::Debug + Clone + Ord> Range<I> {
+    /// Iterate line by line from `start` to `end`, see [Rect::new].
     pub fn new(start: Coord<I>, end: Coord<I>) -> Result<Self> {
-        ensure!(
-            start <= end,
-            "You can’t create a range starting after the end: {:?} {:?}",
-            start,
-            end
-        );
-        Ok(Self {
-            start: start.clone(),
-            current: start,
-            finished: false,
-            end,
-        })
+        Ok(Rect::new(start, end)?.iter())
     }
 
-    /// Return `true` if the given coordinate is contained in the [Range].
+    /// Iterate over the rectangle having `from` and `to` as opposite corners.
+    /// The walk starts on `from` and each line (or column) is walked toward `to`.
+    /// ```
+    /// use aoc::{Coord, Range};
+    ///
+    /// let coords: Vec<_> = Range::between(Coord::at(1, 0), Coord::at(0, 1)).collect();
+    ///
+    /// assert_eq!(coords, vec![Coord::at(1, 0), Coord::at(0, 0), Coord::at(1, 1), Coord::at(0, 1)]);
+    /// ```
+    pub fn between(from: Coord<I>, to: Coord<I>) -> Self {
+        let rect = Rect::new(
+            Coord::at(
+                from.x.clone().min(to.x.clone()),
+                from.y.clone().min(to.y.clone()),
+            ),
+            Coord::at(
+                from.x.clone().max(to.x.clone()),
+                from.y.clone().max(to.y.clone()),
+            ),
+        )
+        .expect("The corners were sorted");
+        Self::walk(rect, from, to)
+    }
+
+    /// Return `true` if the given coordinate is contained in the rectangle of the [Range].
+    /// The coordinates skipped by [Range::stepped] are still contained in the range.
     ///
     /// # Example
     ///
@@ -47,33 +218,158 @@ impl<I: fmt::Debug + Clone + Ord> Range<I> {
     ///
     /// ```
     pub fn contains(&self, other: impl Into<Coord<I>>) -> bool {
-        let other: Coord<I> = other.into();
+        self.rect.contains(other)
+    }
+}
 
-        (&self.start.x..=&self.end.x).contains(&&other.x)
-            && (&self.start.y..=&self.end.y).contains(&&other.y)
+impl<I> Range<I>
+where
+    I: num::One + num::WideOp + Ord + Clone + fmt::Debug,
+{
+    /// Only yield one column every `step.x` columns and one line every `step.y` lines.
+    /// The iteration restarts from the beginning. Returns an error if a step is lower than one.
+    /// ```
+    /// use aoc::Coord;
+    ///
+    /// let range = Coord::at(0, 0).to(Coord::at(4, 2)).unwrap().stepped(Coord::at(2, 2)).unwrap();
+    /// let coords: Vec<_> = range.collect();
+    ///
+    /// assert_eq!(coords, vec![
+    ///     Coord::at(0, 0), Coord::at(2, 0), Coord::at(4, 0),
+    ///     Coord::at(0, 2), Coord::at(2, 2), Coord::at(4, 2),
+    /// ]);
+    /// ```
+    pub fn stepped(mut self, step: Coord<I>) -> Result<Self> {
+        ensure!(
+            step.x >= I::one() && step.y >= I::one(),
+            "You can’t walk a range with a step of {:?}",
+            step
+        );
+        self.step = Some(step);
+        self.cursor = None;
+        Ok(self)
+    }
+
+    fn step(&self) -> Coord<I> {
+        self.step
+            .clone()
+            .unwrap_or_else(|| Coord::at(I::one(), I::one()))
+    }
+
+    /// The cursor of a range that didn't move yet.
+    fn start(&self) -> Cursor {
+        let step = self.step();
+        let columns = Self::steps(self.from.x.clone(), self.to.x.clone(), step.x);
+        let lines = Self::steps(self.from.y.clone(), self.to.y.clone(), step.y);
+        Cursor::new(match self.order {
+            Order::Rows => (lines, columns),
+            Order::Columns => (columns, lines),
+        })
+    }
+
+    fn cursor(&mut self) -> &mut Cursor {
+        if self.cursor.is_none() {
+            self.cursor = Some(self.start());
+        }
+        self.cursor.as_mut().expect("The cursor was just set")
+    }
+
+    /// The distance between two walked values, a step is never lower than one.
+    fn stride(step: I) -> u128 {
+        step.wide_distance(I::one()) + 1
+    }
+
+    /// The number of steps from `from` to the last value walked toward `to`.
+    fn steps(from: I, to: I, step: I) -> u128 {
+        from.wide_distance(to) / Self::stride(step)
+    }
+
+    /// The `n`th value walked from `from` toward `to`.
+    fn nth_value(from: I, to: I, step: I, n: u128) -> I {
+        // `n` steps never go past `to`, so the distance fits in a `u128`. It's applied in chunks
+        // fitting in an `i128`.
+        let mut distance = Self::stride(step) * n;
+        let forward = from <= to;
+        let mut value = from;
+        while distance > 0 {
+            let chunk = distance.min(i128::MAX as u128);
+            distance -= chunk;
+            let offset = if forward {
+                chunk as i128
+            } else {
+                -(chunk as i128)
+            };
+            value = value
+                .clone()
+                .checked_offset(offset)
+                .unwrap_or_else(|| panic!("Can't walk {n} steps toward {to:?}"));
+        }
+        value
+    }
+
+    fn coord_at(&self, (major, minor): (u128, u128)) -> Coord<I> {
+        let step = self.step();
+        let (column, line) = match self.order {
+            Order::Rows => (minor, major),
+            Order::Columns => (major, minor),
+        };
+        Coord::at(
+            Self::nth_value(self.from.x.clone(), self.to.x.clone(), step.x, column),
+            Self::nth_value(self.from.y.clone(), self.to.y.clone(), step.y, line),
+        )
     }
 }
 
-impl<I: Clone + fmt::Debug + Eq + ops::AddAssign + crate::num::One> Iterator for Range<I> {
+impl<I> Iterator for Range<I>
+where
+    I: num::One + num::WideOp + Ord + Clone + fmt::Debug,
+{
     type Item = Coord<I>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let res = self.current.clone();
-        if self.finished {
-            return None;
-        }
-        if self.current.x == self.end.x && self.current.y == self.end.y {
-            self.finished = true;
-        } else if self.current.x == self.end.x {
-            self.current.x = self.start.x.clone();
-            self.current.y += I::one();
-        } else {
-            self.current.x += I::one();
+        let position = self.cursor().next()?;
+        Some(self.coord_at(position))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let cursor = self.cursor.unwrap_or_else(|| self.start());
+        match cursor
+            .remaining()
+            .and_then(|remaining| usize::try_from(remaining).ok())
+        {
+            Some(len) => (len, Some(len)),
+            None => (usize::MAX, None),
         }
-        Some(res)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.cursor().skip(n);
+        self.next()
+    }
+}
+
+impl<I> DoubleEndedIterator for Range<I>
+where
+    I: num::One + num::WideOp + Ord + Clone + fmt::Debug,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let position = self.cursor().next_back()?;
+        Some(self.coord_at(position))
     }
 }
 
+/// The length saturates to [usize::MAX] when there are too many coordinates to count them.
+impl<I> ExactSizeIterator for Range<I>
+where
+    I: num::One + num::WideOp + Ord + Clone + fmt::Debug,
+{
+    fn len(&self) -> usize {
+        self.size_hint().0
+    }
+}
+
+impl<I> FusedIterator for Range<I> where I: num::One + num::WideOp + Ord + Clone + fmt::Debug {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,4 +383,100 @@ mod tests {
         assert_eq!(iter.next(), Some(Coord::at(2, 0)));
         assert_eq!(iter.next(), Some(Coord::at(0, 1)));
     }
+
+    #[test]
+    fn reversed_matches_forward() {
+        let corners = [
+            Coord::at(-2, 3),
+            Coord::at(4, 3),
+            Coord::at(-2, -1),
+            Coord::at(4, -1),
+        ];
+
+        for (from, to) in itertools::iproduct!(corners, corners) {
+            for step in [Coord::at(1, 1), Coord::at(2, 3), Coord::at(5, 1)] {
+                let range = Range::between(from, to).stepped(step).unwrap();
+                let forward: Vec<_> = range.clone().collect();
+                let mut backward: Vec<_> = range.clone().rev().collect();
+                backward.reverse();
+
+                assert_eq!(forward, backward);
+                assert_eq!(forward.len(), range.len());
+                assert_eq!(forward[0], from);
+                assert!(forward.iter().all(|coord| range.contains(*coord)));
+
+                let columns: Vec<_> = range.clone().column_major().collect();
+                assert_eq!(columns.len(), forward.len());
+                assert_eq!(columns.first(), forward.first());
+                assert_eq!(columns.last(), forward.last());
+            }
+        }
+    }
+
+    #[test]
+    fn len_while_iterating() {
+        let mut range = Coord::at(0_u8, 0).to(Coord::at(3, 2)).unwrap();
+
+        for left in (0..12).rev() {
+            if left % 2 == 0 {
+                range.next();
+            } else {
+                range.next_back();
+            }
+            assert_eq!(range.len(), left);
+        }
+        assert_eq!(range.next(), None);
+        assert_eq!(range.next_back(), None);
+        assert!(range.stepped(Coord::at(0, 1)).is_err());
+    }
+
+    #[test]
+    fn whole_type() {
+        let range = Range::between(Coord::at(i8::MAX, 0), Coord::at(i8::MIN, 1));
+        assert_eq!(range.len(), 512);
+        assert_eq!(range.clone().next(), Some(Coord::at(i8::MAX, 0)));
+        assert_eq!(range.clone().nth(255), Some(Coord::at(i8::MIN, 0)));
+        assert_eq!(range.clone().next_back(), Some(Coord::at(i8::MIN, 1)));
+
+        let range = Coord::at(0_u8, u8::MAX)
+            .to(Coord::at(u8::MAX, u8::MAX))
+            .unwrap();
+        let stepped = range.clone().stepped(Coord::at(100, 1)).unwrap();
+        assert_eq!(range.len(), 256);
+        assert_eq!(
+            stepped.map(|coord| coord.x).collect::<Vec<_>>(),
+            vec![0, 100, 200]
+        );
+    }
+
+    #[test]
+    fn too_many_to_count() {
+        let (min, max) = (Coord::at(i64::MIN, i64::MIN), Coord::at(i64::MAX, i64::MAX));
+        let mut range = min.to(max).unwrap();
+        assert_eq!(range.size_hint(), (usize::MAX, None));
+        assert_eq!(range.len(), usize::MAX);
+        assert_eq!(range.next(), Some(min));
+        assert_eq!(range.next(), Some(Coord::at(i64::MIN + 1, i64::MIN)));
+        assert_eq!(range.next_back(), Some(max));
+        assert_eq!(range.nth(2), Some(Coord::at(i64::MIN + 4, i64::MIN)));
+
+        let mut range = Range::between(max, min).column_major();
+        assert_eq!(range.next(), Some(max));
+        assert_eq!(range.next(), Some(Coord::at(i64::MAX, i64::MAX - 1)));
+        assert_eq!(range.next_back(), Some(min));
+
+        let mut range = Coord::at(0_u128, 0).to(Coord::at(u128::MAX, 1)).unwrap();
+        assert_eq!(range.len(), usize::MAX);
+        assert_eq!(range.nth(3), Some(Coord::at(3, 0)));
+        assert_eq!(range.next_back(), Some(Coord::at(u128::MAX, 1)));
+        assert_eq!(range.next_back(), Some(Coord::at(u128::MAX - 1, 1)));
+
+        let mut line = Coord::at(0_u64, 0).to(Coord::at(u64::MAX, 0)).unwrap();
+        assert_eq!(line.len(), usize::MAX);
+        assert_eq!(line.nth(usize::MAX - 1), Some(Coord::at(u64::MAX - 1, 0)));
+        assert_eq!(line.len(), 1);
+        assert_eq!(line.next(), Some(Coord::at(u64::MAX, 0)));
+        assert_eq!(line.next(), None);
+        assert_eq!(line.next_back(), None);
+    }
 }
@@ -0,0 +1,359 @@
+//! Define a [Rect], an immutable rectangle of [Coord]s supporting set operations.
+//!
+//! To iterate over the coordinates of a [Rect], see [Rect::iter] and [Rect::iter_columns].
+
+use crate::{num, range::Range, BoundingBox, Coord};
+use anyhow::{ensure, Result};
+use std::{fmt, ops};
+
+/// A rectangle going from the `start` (top left) corner to the `end` (bottom right) corner.
+/// Both corners are included in the rectangle, thus a [Rect] is never empty.
+/// ```
+/// use aoc::{Coord, Rect};
+///
+/// let rect = Rect::new(Coord::at(0, 0), Coord::at(3, 1)).unwrap();
+///
+/// assert_eq!(rect.area(), 8);
+/// assert!(rect.contains((2, 1)));
+/// assert!(Rect::new(Coord::at(3, 0), Coord::at(0, 1)).is_err());
+/// ```
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct Rect<I = usize> {
+    start: Coord<I>,
+    end: Coord<I>,
+}
+
+impl<I> Rect<I> {
+    /// The top left corner of the rectangle.
+    pub fn start(&self) -> &Coord<I> {
+        &self.start
+    }
+
+    /// The bottom right corner of the rectangle.
+    pub fn end(&self) -> &Coord<I> {
+        &self.end
+    }
+}
+
+impl<I: Ord + Clone + fmt::Debug> Rect<I> {
+    /// Create a new rectangle, returns an error if `start` is not the top left corner of the
+    /// rectangle.
+    pub fn new(start: Coord<I>, end: Coord<I>) -> Result<Self> {
+        ensure!(
+            start.x <= end.x && start.y <= end.y,
+            "You can’t create a rectangle starting after the end: {:?} {:?}",
+            start,
+            end
+        );
+        Ok(Self { start, end })
+    }
+
+    /// Return `true` if the given coordinate is contained in the [Rect].
+    /// ```
+    /// use aoc::{Coord, Rect};
+    ///
+    /// let rect = Rect::new(Coord::at(10, 10), Coord::at(15, 15)).unwrap();
+    ///
+    /// assert!(rect.contains((12, 13)));
+    /// assert!(rect.contains((15, 15)));
+    /// assert!(!rect.contains((14, 16)));
+    /// ```
+    pub fn contains(&self, other: impl Into<Coord<I>>) -> bool {
+        let other: Coord<I> = other.into();
+
+        (&self.start.x..=&self.end.x).contains(&&other.x)
+            && (&self.start.y..=&self.end.y).contains(&&other.y)
+    }
+
+    /// Return the rectangle shared by both rectangles.
+    /// ```
+    /// use aoc::{Coord, Rect};
+    ///
+    /// let left = Rect::new(Coord::at(0, 0), Coord::at(4, 4)).unwrap();
+    /// let right = Rect::new(Coord::at(2, 3), Coord::at(6, 8)).unwrap();
+    ///
+    /// assert_eq!(left.intersection(&right), Rect::new(Coord::at(2, 3), Coord::at(4, 4)).ok());
+    /// assert_eq!(left.intersection(&Rect::new(Coord::at(5, 0), Coord::at(5, 0)).unwrap()), None);
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let start = Coord::at(
+            self.start.x.clone().max(other.start.x.clone()),
+            self.start.y.clone().max(other.start.y.clone()),
+        );
+        let end = Coord::at(
+            self.end.x.clone().min(other.end.x.clone()),
+            self.end.y.clone().min(other.end.y.clone()),
+        );
+        Self::new(start, end).ok()
+    }
+
+    /// Return the smallest rectangle containing both rectangles.
+    /// ```
+    /// use aoc::{Coord, Rect};
+    ///
+    /// let left = Rect::new(Coord::at(0, 3), Coord::at(1, 4)).unwrap();
+    /// let right = Rect::new(Coord::at(3, 1), Coord::at(3, 1)).unwrap();
+    ///
+    /// assert_eq!(left.union_hull(&right), Rect::new(Coord::at(0, 1), Coord::at(3, 4)).unwrap());
+    /// ```
+    pub fn union_hull(&self, other: &Self) -> Self {
+        Self {
+            start: Coord::at(
+                self.start.x.clone().min(other.start.x.clone()),
+                self.start.y.clone().min(other.start.y.clone()),
+            ),
+            end: Coord::at(
+                self.end.x.clone().max(other.end.x.clone()),
+                self.end.y.clone().max(other.end.y.clone()),
+            ),
+        }
+    }
+
+    /// Return an iterator over all the coordinates of the rectangle, line by line.
+    /// See [Range] to walk the rectangle in other orders.
+    /// ```
+    /// use aoc::{Coord, Rect};
+    ///
+    /// let rect = Rect::new(Coord::at(0, 0), Coord::at(1, 1)).unwrap();
+    /// let coords: Vec<_> = rect.iter().collect();
+    ///
+    /// assert_eq!(coords, vec![Coord::at(0, 0), Coord::at(1, 0), Coord::at(0, 1), Coord::at(1, 1)]);
+    /// ```
+    pub fn iter(&self) -> Range<I> {
+        Range::walk(self.clone(), self.start.clone(), self.end.clone())
+    }
+
+    /// Return an iterator over all the coordinates of the rectangle, column by column.
+    /// ```
+    /// use aoc::{Coord, Rect};
+    ///
+    /// let rect = Rect::new(Coord::at(0, 0), Coord::at(1, 1)).unwrap();
+    /// let coords: Vec<_> = rect.iter_columns().collect();
+    ///
+    /// assert_eq!(coords, vec![Coord::at(0, 0), Coord::at(0, 1), Coord::at(1, 0), Coord::at(1, 1)]);
+    /// ```
+    pub fn iter_columns(&self) -> Range<I> {
+        self.iter().column_major()
+    }
+}
+
+impl<I> Rect<I>
+where
+    I: ops::Sub<Output = I> + ops::Add<Output = I> + num::One + Ord + Clone + fmt::Debug,
+{
+    /// The number of columns in the rectangle.
+    pub fn width(&self) -> I {
+        self.end.x.clone() - self.start.x.clone() + I::one()
+    }
+
+    /// The number of lines in the rectangle.
+    pub fn height(&self) -> I {
+        self.end.y.clone() - self.start.y.clone() + I::one()
+    }
+
+    /// The number of coordinates in the rectangle.
+    pub fn area(&self) -> I
+    where
+        I: ops::Mul<Output = I>,
+    {
+        self.width() * self.height()
+    }
+
+    /// Split the rectangle in two, the first part contains all the columns before `x` and the
+    /// second one all the columns starting at `x`.
+    /// ```
+    /// use aoc::{Coord, Rect};
+    ///
+    /// let rect = Rect::new(Coord::at(0, 0), Coord::at(4, 2)).unwrap();
+    ///
+    /// let (left, right) = rect.split_at_x(3);
+    /// assert_eq!(left, Rect::new(Coord::at(0, 0), Coord::at(2, 2)).ok());
+    /// assert_eq!(right, Rect::new(Coord::at(3, 0), Coord::at(4, 2)).ok());
+    ///
+    /// assert_eq!(rect.split_at_x(0), (None, Some(rect)));
+    /// assert_eq!(rect.split_at_x(5), (Some(rect), None));
+    /// ```
+    pub fn split_at_x(&self, x: I) -> (Option<Self>, Option<Self>) {
+        let left = (x > self.start.x).then(|| Self {
+            start: self.start.clone(),
+            end: Coord::at(
+                (x.clone() - I::one()).min(self.end.x.clone()),
+                self.end.y.clone(),
+            ),
+        });
+        let right = (x <= self.end.x).then(|| Self {
+            start: Coord::at(x.max(self.start.x.clone()), self.start.y.clone()),
+            end: self.end.clone(),
+        });
+        (left, right)
+    }
+
+    /// Split the rectangle in two, the first part contains all the lines before `y` and the
+    /// second one all the lines starting at `y`.
+    /// ```
+    /// use aoc::{Coord, Rect};
+    ///
+    /// let rect = Rect::new(Coord::at(0, 0), Coord::at(4, 2)).unwrap();
+    ///
+    /// let (top, bottom) = rect.split_at_y(1);
+    /// assert_eq!(top, Rect::new(Coord::at(0, 0), Coord::at(4, 0)).ok());
+    /// assert_eq!(bottom, Rect::new(Coord::at(0, 1), Coord::at(4, 2)).ok());
+    /// ```
+    pub fn split_at_y(&self, y: I) -> (Option<Self>, Option<Self>) {
+        let top = (y > self.start.y).then(|| Self {
+            start: self.start.clone(),
+            end: Coord::at(
+                self.end.x.clone(),
+                (y.clone() - I::one()).min(self.end.y.clone()),
+            ),
+        });
+        let bottom = (y <= self.end.y).then(|| Self {
+            start: Coord::at(self.start.x.clone(), y.max(self.start.y.clone())),
+            end: self.end.clone(),
+        });
+        (top, bottom)
+    }
+
+    /// Remove `other` from the rectangle. The remaining coordinates are returned as up to four
+    /// non overlapping rectangles, in order the top, left, right and bottom parts.
+    /// ```
+    /// use aoc::{Coord, Rect};
+    ///
+    /// let rect = Rect::new(Coord::at(0, 0), Coord::at(4, 4)).unwrap();
+    /// let hole = Rect::new(Coord::at(1, 1), Coord::at(2, 3)).unwrap();
+    ///
+    /// let parts = rect.subtract(&hole);
+    /// assert_eq!(parts.len(), 4);
+    /// assert_eq!(parts.iter().map(|part| part.area()).sum::<usize>(), 25 - 6);
+    ///
+    /// assert_eq!(rect.subtract(&rect), vec![]);
+    /// ```
+    pub fn subtract(&self, other: &Self) -> Vec<Self>
+    where
+        I: num::CheckedOp,
+    {
+        let Some(hole) = self.intersection(other) else {
+            return vec![self.clone()];
+        };
+        subtract_along_axes(
+            self.clone(),
+            [(1, hole.start.y, hole.end.y), (0, hole.start.x, hole.end.x)],
+            |rect, axis, value| match axis {
+                0 => rect.split_at_x(value),
+                _ => rect.split_at_y(value),
+            },
+        )
+    }
+}
+
+/// Remove a hole from a box by cutting the box around the hole along each axis in turn, the
+/// hole is given by its `(axis, first, last)` bounds and must be contained in the box. The
+/// parts before the hole are returned first in the order of the axes, followed by the parts
+/// after the hole in the reverse order. There is no part after a hole ending on the biggest
+/// value of the type. Shared by [Rect::subtract] and [crate::Cuboid::subtract].
+pub(crate) fn subtract_along_axes<B, I>(
+    shape: B,
+    hole: impl IntoIterator<Item = (usize, I, I)>,
+    split_at: impl Fn(&B, usize, I) -> (Option<B>, Option<B>),
+) -> Vec<B>
+where
+    I: num::CheckedOp + num::One,
+{
+    let mut before = Vec::new();
+    let mut after = Vec::new();
+    let mut rest = shape;
+    for (axis, first, last) in hole {
+        let (part, middle) = split_at(&rest, axis, first);
+        before.extend(part);
+        rest = middle.expect("The hole is contained in the shape");
+        if let Some(after_last) = last.checked_add(I::one()) {
+            let (middle, part_after) = split_at(&rest, axis, after_last);
+            after.extend(part_after);
+            rest = middle.expect("The hole is contained in the shape");
+        }
+    }
+    before.extend(after.into_iter().rev());
+    before
+}
+
+impl<I> IntoIterator for Rect<I>
+where
+    I: num::One + num::WideOp + Ord + Clone + fmt::Debug,
+{
+    type Item = Coord<I>;
+    type IntoIter = Range<I>;
+
+    fn into_iter(self) -> Range<I> {
+        self.iter()
+    }
+}
+
+impl<I> From<BoundingBox<I>> for Rect<I> {
+    fn from(bbox: BoundingBox<I>) -> Self {
+        Self {
+            start: bbox.min,
+            end: bbox.max,
+        }
+    }
+}
+
+impl<I> From<Rect<I>> for BoundingBox<I> {
+    fn from(rect: Rect<I>) -> Self {
+        Self {
+            min: rect.start,
+            max: rect.end,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subtract_matches_contains() {
+        let rect = Rect::new(Coord::at(-3, -2), Coord::at(4, 3)).unwrap();
+
+        for start in Coord::at(-5, -5).to(Coord::at(5, 5)).unwrap() {
+            let other = Rect::new(start, start + Coord::at(2, 3)).unwrap();
+            let parts = rect.subtract(&other);
+            assert!(parts.len() <= 4);
+
+            for coord in rect.iter() {
+                let covered = parts.iter().filter(|part| part.contains(coord)).count();
+                let expected = usize::from(!other.contains(coord));
+                assert_eq!(covered, expected, "{coord:?} removing {other:?}");
+            }
+            for part in &parts {
+                assert_eq!(part.intersection(&rect), Some(*part));
+                assert_eq!(part.intersection(&other), None);
+            }
+        }
+    }
+
+    #[test]
+    fn iterating_keeps_the_rect() {
+        let rect = Rect::new(Coord::at(1_u8, 2), Coord::at(3, 4)).unwrap();
+
+        assert_eq!(rect.iter().count(), 9);
+        assert_eq!(rect.iter_columns().count(), 9);
+        assert_eq!(rect.into_iter().next_back(), Some(Coord::at(3, 4)));
+        assert_eq!(rect.iter_columns().next_back(), Some(Coord::at(3, 4)));
+        assert_eq!(rect.area(), 9);
+        assert_eq!(rect.union_hull(&rect), rect);
+    }
+
+    #[test]
+    fn bounds_of_the_type() {
+        let rect = Rect::new(Coord::at(0_u8, 0), Coord::at(u8::MAX, 3)).unwrap();
+        let hole = Rect::new(Coord::at(200, 1), Coord::at(u8::MAX, u8::MAX)).unwrap();
+        assert_eq!(
+            rect.subtract(&hole),
+            vec![
+                Rect::new(Coord::at(0, 0), Coord::at(u8::MAX, 0)).unwrap(),
+                Rect::new(Coord::at(0, 1), Coord::at(199, 3)).unwrap(),
+            ]
+        );
+        assert_eq!(rect.subtract(&rect), vec![]);
+    }
+}
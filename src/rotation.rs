@@ -0,0 +1,326 @@
+//! Define a [Rotation] that can be composed and applied to [Coord]s, [Shape]s and 3D points.
+
+use crate::{Coord, Shape};
+use itertools::Itertools;
+use std::ops::{Mul, Neg};
+
+/// A rotation (or reflection) of the space by a multiple of a quarter of turn around the axes.
+///
+/// It's stored as a 3x3 matrix only containing `-1`, `0` and `1`. When applied on a 2D [Coord]
+/// the `z` axis is ignored, thus only the rotations around the `z` axis and the reflections of
+/// the `x` and `y` axes makes sense in 2D.
+///
+/// The rotations are composed like matrices, `a * b` is the rotation applying `b` and then `a`.
+/// ```
+/// use aoc::{Coord, Rotation};
+///
+/// let rotation = Rotation::clockwise() * Rotation::reflect_x();
+///
+/// assert_eq!(rotation * Coord::at(3, 1), Coord::at(3, 1).reflect_x().rotate_clockwise());
+/// assert_eq!(rotation * rotation.inverse(), Rotation::identity());
+/// ```
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq)]
+pub struct Rotation {
+    matrix: [[i8; 3]; 3],
+}
+
+/// The default rotation is the identity
+impl Default for Rotation {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Rotation {
+    /// The rotation that doesn't move anything.
+    pub fn identity() -> Self {
+        Self {
+            matrix: [[1, 0, 0], [0, 1, 0], [0, 0, 1]],
+        }
+    }
+
+    /// A quarter turn clockwise around the `z` axis, same as [Coord::rotate_clockwise].
+    pub fn clockwise() -> Self {
+        Self {
+            matrix: [[0, -1, 0], [1, 0, 0], [0, 0, 1]],
+        }
+    }
+
+    /// A quarter turn counter clockwise around the `z` axis, same as [Coord::rotate_counter_clockwise].
+    pub fn counter_clockwise() -> Self {
+        Self::clockwise().inverse()
+    }
+
+    /// Rotate clockwise `n` quarter turns around the `z` axis.
+    /// A negative number of quarter turns rotate counter clockwise.
+    /// ```
+    /// use aoc::{Coord, Rotation};
+    ///
+    /// assert_eq!(Rotation::quarter_turns(2) * Coord::at(3, 1), Coord::at(-3, -1));
+    /// assert_eq!(Rotation::quarter_turns(-1), Rotation::counter_clockwise());
+    /// assert_eq!(Rotation::quarter_turns(4), Rotation::identity());
+    /// ```
+    pub fn quarter_turns(n: isize) -> Self {
+        (0..n.rem_euclid(4)).fold(Self::identity(), |rotation, _| Self::clockwise() * rotation)
+    }
+
+    /// Negate the `x` axis, same as [Coord::reflect_x].
+    pub fn reflect_x() -> Self {
+        Self {
+            matrix: [[-1, 0, 0], [0, 1, 0], [0, 0, 1]],
+        }
+    }
+
+    /// Negate the `y` axis, same as [Coord::reflect_y].
+    pub fn reflect_y() -> Self {
+        Self {
+            matrix: [[1, 0, 0], [0, -1, 0], [0, 0, 1]],
+        }
+    }
+
+    /// Negate the `z` axis.
+    pub fn reflect_z() -> Self {
+        Self {
+            matrix: [[1, 0, 0], [0, 1, 0], [0, 0, -1]],
+        }
+    }
+
+    /// Returns the rotation that cancels this one.
+    pub fn inverse(&self) -> Self {
+        let mut matrix = [[0; 3]; 3];
+        for (row, line) in self.matrix.iter().enumerate() {
+            for (col, value) in line.iter().enumerate() {
+                matrix[col][row] = *value;
+            }
+        }
+        Self { matrix }
+    }
+
+    /// Returns `true` if the rotation contains a reflection.
+    /// ```
+    /// use aoc::Rotation;
+    ///
+    /// assert!(!Rotation::clockwise().is_reflection());
+    /// assert!(Rotation::reflect_x().is_reflection());
+    /// assert!(!(Rotation::reflect_x() * Rotation::reflect_y()).is_reflection());
+    /// ```
+    pub fn is_reflection(&self) -> bool {
+        self.determinant() < 0
+    }
+
+    /// Returns `true` if the rotation doesn't move the `z` axis and can be applied on 2D
+    /// coordinates.
+    pub fn is_planar(&self) -> bool {
+        self.matrix[2] == [0, 0, 1]
+    }
+
+    /// Returns the 8 orientations of a 2D plane, the 4 rotations followed by the 4 reflections.
+    /// ```
+    /// use aoc::Rotation;
+    ///
+    /// let all = Rotation::all_2d();
+    /// assert_eq!(all[0], Rotation::identity());
+    /// assert_eq!(all[1], Rotation::clockwise());
+    /// assert!(all.iter().all(|rotation| rotation.is_planar()));
+    /// ```
+    pub fn all_2d() -> [Self; 8] {
+        let mut ret = [Self::identity(); 8];
+        for turns in 0..4 {
+            ret[turns] = Self::quarter_turns(turns as isize);
+            ret[turns + 4] = ret[turns] * Self::reflect_x();
+        }
+        ret
+    }
+
+    /// Returns the 24 rotations of the 3D space, the reflections are not included.
+    /// ```
+    /// use aoc::Rotation;
+    ///
+    /// let all = Rotation::all_3d();
+    /// assert_eq!(all.len(), 24);
+    /// assert_eq!(all[0], Rotation::identity());
+    /// assert!(all.iter().all(|rotation| !rotation.is_reflection()));
+    /// ```
+    pub fn all_3d() -> Vec<Self> {
+        (0..3)
+            .permutations(3)
+            .cartesian_product(0..8)
+            .map(|(axes, signs)| {
+                let mut matrix = [[0; 3]; 3];
+                for (row, col) in axes.into_iter().enumerate() {
+                    matrix[row][col] = if signs & (1 << row) == 0 { 1 } else { -1 };
+                }
+                Self { matrix }
+            })
+            .filter(|rotation| !rotation.is_reflection())
+            .collect()
+    }
+
+    fn determinant(&self) -> i8 {
+        let m = &self.matrix;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    /// Compute one component of the rotated vector.
+    /// Since there is only one non-zero value per row we never need to add or multiply anything.
+    fn component<I: Neg<Output = I> + Clone>(&self, row: usize, vector: &[&I]) -> I {
+        let col = self.matrix[row]
+            .iter()
+            .position(|value| *value != 0)
+            .expect("Corrupted rotation");
+        if self.matrix[row][col] < 0 {
+            -vector[col].clone()
+        } else {
+            vector[col].clone()
+        }
+    }
+}
+
+impl Mul for Rotation {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let mut matrix = [[0; 3]; 3];
+        for (row, line) in matrix.iter_mut().enumerate() {
+            for (col, value) in line.iter_mut().enumerate() {
+                *value = (0..3)
+                    .map(|i| self.matrix[row][i] * rhs.matrix[i][col])
+                    .sum();
+            }
+        }
+        Self { matrix }
+    }
+}
+
+impl<I: Neg<Output = I> + Clone> Mul<Coord<I>> for Rotation {
+    type Output = Coord<I>;
+
+    /// Apply the rotation on a 2D [Coord].
+    /// The rotation must be [planar](Rotation::is_planar).
+    /// ```
+    /// use aoc::{Coord, Rotation};
+    ///
+    /// assert_eq!(Rotation::clockwise() * Coord::at(3, 1), Coord::at(-1, 3));
+    /// assert_eq!(Rotation::reflect_y() * Coord::at(3, 1), Coord::at(3, -1));
+    /// ```
+    fn mul(self, coord: Coord<I>) -> Coord<I> {
+        debug_assert!(
+            self.is_planar(),
+            "Can't apply a 3D rotation on a 2D coordinate"
+        );
+        let vector = [&coord.x, &coord.y];
+        Coord::at(self.component(0, &vector), self.component(1, &vector))
+    }
+}
+
+impl<I: Neg<Output = I> + Clone> Mul<(I, I, I)> for Rotation {
+    type Output = (I, I, I);
+
+    /// Apply the rotation on a 3D point in the form `(x, y, z)`.
+    /// ```
+    /// use aoc::Rotation;
+    ///
+    /// let point = (1, 2, 3);
+    /// let rotated: Vec<_> = Rotation::all_3d().into_iter().map(|rotation| rotation * point).collect();
+    ///
+    /// assert!(rotated.contains(&(3, 1, 2)));
+    /// assert!(rotated.contains(&(-1, -2, 3)));
+    /// assert!(!rotated.contains(&(-1, 2, 3)));
+    /// ```
+    fn mul(self, (x, y, z): (I, I, I)) -> (I, I, I) {
+        let vector = [&x, &y, &z];
+        (
+            self.component(0, &vector),
+            self.component(1, &vector),
+            self.component(2, &vector),
+        )
+    }
+}
+
+impl Mul<Shape> for Rotation {
+    type Output = Shape;
+
+    /// Apply the rotation on every point of a [Shape] around the origin.
+    fn mul(self, shape: Shape) -> Shape {
+        shape.map(|coord| self * coord)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn all_3d_are_distinct_and_closed() {
+        let all = Rotation::all_3d();
+        let set: HashSet<_> = all.iter().copied().collect();
+        assert_eq!(set.len(), 24);
+
+        for left in &all {
+            assert!(set.contains(&left.inverse()));
+            assert_eq!(*left * left.inverse(), Rotation::identity());
+            for right in &all {
+                assert!(set.contains(&(*left * *right)));
+            }
+        }
+    }
+
+    #[test]
+    fn match_coord_methods() {
+        let coord = Coord::<isize>::at(3, 1);
+
+        for n in 0..8 {
+            assert_eq!(
+                Rotation::quarter_turns(n) * coord,
+                coord.rotate_clockwise_n(n as usize)
+            );
+            assert_eq!(
+                Rotation::quarter_turns(-n) * coord,
+                coord.rotate_counter_clockwise_n(n as usize)
+            );
+        }
+        assert_eq!(Rotation::reflect_x() * coord, coord.reflect_x());
+        assert_eq!(Rotation::reflect_y() * coord, coord.reflect_y());
+        assert_eq!(
+            (Rotation::clockwise() * Rotation::reflect_y()) * coord,
+            coord.reflect_y().rotate_clockwise()
+        );
+    }
+
+    #[test]
+    fn all_2d_are_distinct() {
+        let coord = Coord::<isize>::at(3, 1);
+        let set: HashSet<_> = Rotation::all_2d()
+            .into_iter()
+            .map(|rotation| rotation * coord)
+            .collect();
+        assert_eq!(set.len(), 8);
+    }
+
+    #[test]
+    fn rotate_shape() {
+        let shape = Shape::from_coords([(0, 0), (1, 0), (2, 0), (2, 1)]);
+        insta::assert_display_snapshot!(shape, @r###"
+           0 1 2
+        0  # # # 
+        1  . . # 
+        "###);
+        let shape = Rotation::clockwise() * shape;
+        insta::assert_display_snapshot!(shape, @r###"
+          -1 0
+        0  . # 
+        1  . # 
+        2  # # 
+        "###);
+        let shape = Rotation::reflect_x() * shape;
+        insta::assert_display_snapshot!(shape, @r###"
+           0 1
+        0  # . 
+        1  # . 
+        2  # # 
+        "###);
+    }
+}
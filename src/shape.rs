@@ -4,7 +4,7 @@ use std::{
     ops::{Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign},
 };
 
-use crate::Coord;
+use crate::{iterator::BoundingBoxExt, BoundingBox, Coord};
 
 #[derive(Debug)]
 pub struct Shape {
@@ -49,6 +49,14 @@ impl Shape {
         self.coords.is_empty()
     }
 
+    /// Apply a function on every point of the [Shape].
+    pub fn map(self, f: impl FnMut(Coord<isize>) -> Coord<isize>) -> Shape {
+        Shape {
+            coords: self.coords.into_iter().map(f).collect(),
+            displayed_as: self.displayed_as,
+        }
+    }
+
     pub fn collides_with(&self, other: &Shape) -> bool {
         !(self & other).is_empty()
     }
@@ -62,7 +70,7 @@ impl Shape {
     }
 
     pub fn min(&self) -> Option<Coord<isize>> {
-        self.min_x().zip(self.min_y()).map(|(x, y)| Coord::at(x, y))
+        self.bounding_box().map(|bbox| bbox.min)
     }
 
     pub fn max_x(&self) -> Option<isize> {
@@ -74,7 +82,12 @@ impl Shape {
     }
 
     pub fn max(&self) -> Option<Coord<isize>> {
-        self.max_x().zip(self.max_y()).map(|(x, y)| Coord::at(x, y))
+        self.bounding_box().map(|bbox| bbox.max)
+    }
+
+    /// Compute the smallest rectangle containing the whole [Shape] in one pass.
+    pub fn bounding_box(&self) -> Option<BoundingBox<isize>> {
+        self.coords.iter().copied().bounding_box()
     }
 }
 
@@ -130,7 +143,7 @@ impl BitAnd for Shape {
 
 impl BitAndAssign for Shape {
     fn bitand_assign(&mut self, rhs: Self) {
-        *self = std::mem::take(self) | rhs;
+        *self = std::mem::take(self) & rhs;
     }
 }
 
@@ -306,6 +319,19 @@ mod tests {
         "###);
     }
 
+    #[test]
+    fn intersect_shape() {
+        let cross = Shape::from_coords([(0, -1), (-1, 0), (0, 0), (1, 0), (0, 1)]);
+        let line = Shape::from_coords([(-1, 0), (0, 0), (1, 0), (2, 0)]);
+        let intersection = &cross & &line;
+        let mut shape = cross;
+        shape &= line;
+        assert_eq!(shape.to_string(), intersection.to_string());
+        assert!(!shape.contains(Coord::at(0, -1)));
+        assert!(!shape.contains(Coord::at(2, 0)));
+        assert!(shape.contains(Coord::at(1, 0)));
+    }
+
     #[test]
     fn collide_with() {
         let top_left = Shape::from_coords([(0, -1), (-1, 0)]);
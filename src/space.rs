@@ -3,7 +3,7 @@ use std::{
     ops::{Index, IndexMut},
 };
 
-use crate::{shape::Shape, Coord};
+use crate::{shape::Shape, BoundingBox, Coord};
 
 type Id = usize;
 
@@ -54,7 +54,7 @@ impl Space {
     }
 
     pub fn min(&self) -> Option<Coord<isize>> {
-        self.min_x().zip(self.min_y()).map(|(x, y)| Coord::at(x, y))
+        self.bounding_box().map(|bbox| bbox.min)
     }
 
     pub fn max_x(&self) -> Option<isize> {
@@ -74,7 +74,16 @@ impl Space {
     }
 
     pub fn max(&self) -> Option<Coord<isize>> {
-        self.max_x().zip(self.max_y()).map(|(x, y)| Coord::at(x, y))
+        self.bounding_box().map(|bbox| bbox.max)
+    }
+
+    /// Compute the smallest rectangle containing all the [Shape]s.
+    pub fn bounding_box(&self) -> Option<BoundingBox<isize>> {
+        self.shapes
+            .iter()
+            .filter_map(|shape| shape.as_ref())
+            .filter_map(|shape| shape.bounding_box())
+            .reduce(|left, right| left.union(&right))
     }
 }
 
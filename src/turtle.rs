@@ -1,10 +1,57 @@
 use crate::{Coord, Direction, Movement};
 use std::ops::{Add, AddAssign};
 
+/// How a [Turtle] interprets the relative [Movement]s.
+/// The absolute movements (`North`, `East`, ...) always move the turtle without turning it,
+/// except in [TurtleMode::Waypoint].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum TurtleMode {
+    /// `Right(n)` and `Left(n)` turn a quarter of turn and then move `n` steps forward.
+    TurnThenMove,
+    /// `Right(n)` and `Left(n)` turn `n` quarters of turn without moving, `Forward(n)` moves.
+    QuarterTurns,
+    /// `Right(n)` and `Left(n)` turn `n` degrees without moving, `Forward(n)` moves.
+    ///
+    /// # Panics
+    /// Applying a turn whose `n` isn't a multiple of 90 panics.
+    Degrees,
+    /// The absolute movements move the waypoint, `Right(n)` and `Left(n)` rotate the waypoint
+    /// around the turtle by `n` degrees and `Forward(n)` moves the turtle `n` times to the
+    /// waypoint. The waypoint is relative to the turtle.
+    ///
+    /// # Panics
+    /// Applying a turn whose `n` isn't a multiple of 90 panics.
+    Waypoint,
+}
+
+/// The default mode is [TurtleMode::TurnThenMove]
+impl Default for TurtleMode {
+    fn default() -> Self {
+        Self::TurnThenMove
+    }
+}
+
+/// Move on a 2D plane by applying [Movement]s, see [TurtleMode] for how the movements are
+/// interpreted.
+/// ```
+/// use aoc::{Coord, Direction, Movement, Turtle, TurtleMode};
+///
+/// let mut turtle = Turtle::from(Coord::at(0, 0), Direction::East);
+/// turtle += Movement::Right(3);
+/// assert_eq!(turtle.coord, Coord::at(0, 3));
+///
+/// let mut turtle = Turtle::from(Coord::at(0, 0), Direction::East).with_mode(TurtleMode::Degrees);
+/// turtle += Movement::Right(180);
+/// turtle += Movement::Forward(3);
+/// assert_eq!(turtle.coord, Coord::at(-3, 0));
+/// ```
 #[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct Turtle {
     pub coord: Coord<isize>,
     pub facing: Direction,
+    pub(crate) mode: TurtleMode,
+    /// Only used in [TurtleMode::Waypoint].
+    pub(crate) waypoint: Coord<isize>,
 }
 
 impl Turtle {
@@ -13,7 +60,47 @@ impl Turtle {
     }
 
     pub fn from(coord: Coord<isize>, facing: Direction) -> Self {
-        Self { coord, facing }
+        Self {
+            coord,
+            facing,
+            ..Default::default()
+        }
+    }
+
+    /// Change how the turtle interprets the [Movement]s.
+    pub fn with_mode(self, mode: TurtleMode) -> Self {
+        Self { mode, ..self }
+    }
+
+    /// How the turtle interprets the [Movement]s, see [Turtle::with_mode].
+    pub fn mode(&self) -> TurtleMode {
+        self.mode
+    }
+
+    /// The waypoint relative to the turtle, see [Turtle::with_waypoint].
+    pub fn waypoint(&self) -> Coord<isize> {
+        self.waypoint
+    }
+
+    /// Follow a waypoint starting at `waypoint` relatively to the turtle, see
+    /// [TurtleMode::Waypoint].
+    /// ```
+    /// use aoc::{Coord, Movement, Turtle};
+    ///
+    /// let mut ship = Turtle::new().with_waypoint(Coord::at(10, -1));
+    /// for movement in ["F10", "N3", "F7", "R90", "F11"] {
+    ///     ship += movement.parse::<Movement>().unwrap();
+    /// }
+    ///
+    /// assert_eq!(ship.coord, Coord::at(214, 72));
+    /// assert_eq!(ship.distance_from_base(), 286);
+    /// ```
+    pub fn with_waypoint(self, waypoint: Coord<isize>) -> Self {
+        Self {
+            mode: TurtleMode::Waypoint,
+            waypoint,
+            ..self
+        }
     }
 
     pub fn distance_from(&self, coord: &Coord<isize>) -> isize {
@@ -23,41 +110,89 @@ impl Turtle {
     pub fn distance_from_base(&self) -> isize {
         self.coord.manhattan_distance_from_base()
     }
+
+    /// Split a movement into smaller movements going through every coordinate the turtle
+    /// visits when applying it, see [Movement::explode]. The turns that don't move the turtle
+    /// are kept as is.
+    /// ```
+    /// use aoc::{Movement, Turtle, TurtleMode};
+    ///
+    /// let turtle = Turtle::new();
+    /// assert_eq!(turtle.steps(Movement::Right(2)).count(), 2);
+    ///
+    /// let turtle = turtle.with_mode(TurtleMode::Degrees);
+    /// assert_eq!(turtle.steps(Movement::Right(270)).collect::<Vec<_>>(), vec![Movement::Right(270)]);
+    /// ```
+    pub fn steps(&self, movement: Movement) -> impl Iterator<Item = Movement> {
+        let turns_in_place = self.mode != TurtleMode::TurnThenMove
+            && matches!(movement, Movement::Left(_) | Movement::Right(_));
+        let (whole, exploded) = if turns_in_place {
+            (Some(movement), None)
+        } else {
+            (None, Some(movement.explode()))
+        };
+        whole.into_iter().chain(exploded.into_iter().flatten())
+    }
+
+    fn degrees_to_quarter_turns(degrees: isize) -> i32 {
+        assert!(
+            degrees % 90 == 0,
+            "A turtle can only turn by multiples of 90 degrees, not {degrees}"
+        );
+        (degrees / 90) as i32
+    }
+
+    /// Turn clockwise by `quarter_turns`, or rotate the waypoint in [TurtleMode::Waypoint].
+    fn turn(&mut self, quarter_turns: i32) {
+        if self.mode == TurtleMode::Waypoint {
+            self.waypoint = self
+                .waypoint
+                .rotate_clockwise_n(quarter_turns.rem_euclid(4) as usize);
+        } else {
+            self.facing = self.facing.rotate_n(quarter_turns).canonical();
+        }
+    }
 }
 
+/// Apply a [Movement] according to the [TurtleMode] of the turtle.
+///
+/// # Panics
+/// In [TurtleMode::Degrees] and [TurtleMode::Waypoint] if a turn isn't a multiple of 90 degrees.
+/// ```should_panic
+/// use aoc::{Movement, Turtle, TurtleMode};
+///
+/// let _ = Turtle::new().with_mode(TurtleMode::Degrees) + Movement::Right(45);
+/// ```
 impl Add<Movement> for Turtle {
     type Output = Self;
 
     fn add(mut self, other: Movement) -> Self {
-        match (other, self.facing) {
-            (Movement::North(n), _) => self.coord.y -= n,
-            (Movement::West(n), _) => self.coord.x -= n,
-            (Movement::East(n), _) => self.coord.x += n,
-            (Movement::South(n), _) => self.coord.y += n,
-            (Movement::Right(n), Direction::North | Direction::Up)
-            | (Movement::Left(n), Direction::South | Direction::Down)
-            | (Movement::Forward(n), Direction::East | Direction::Right) => {
-                self.facing = Direction::East;
-                self.coord.x += n;
-            }
-            (Movement::Left(n), Direction::North | Direction::Up)
-            | (Movement::Right(n), Direction::South | Direction::Down)
-            | (Movement::Forward(n), Direction::West | Direction::Left) => {
-                self.facing = Direction::West;
-                self.coord.x -= n;
-            }
-            (Movement::Left(n), Direction::East | Direction::Right)
-            | (Movement::Right(n), Direction::West | Direction::Left)
-            | (Movement::Forward(n), Direction::North | Direction::Up) => {
-                self.facing = Direction::North;
-                self.coord.y -= n;
-            }
-            (Movement::Left(n), Direction::West | Direction::Left)
-            | (Movement::Right(n), Direction::East | Direction::Right)
-            | (Movement::Forward(n), Direction::South | Direction::Down) => {
-                self.facing = Direction::South;
-                self.coord.y += n;
+        let (quarter_turns, n) = match (other, self.mode) {
+            (Movement::Right(n), TurtleMode::TurnThenMove) => (1, n),
+            (Movement::Left(n), TurtleMode::TurnThenMove) => (-1, n),
+            (Movement::Right(n), TurtleMode::QuarterTurns) => (n as i32, 0),
+            (Movement::Left(n), TurtleMode::QuarterTurns) => (-n as i32, 0),
+            (Movement::Right(n), _) => (Self::degrees_to_quarter_turns(n), 0),
+            (Movement::Left(n), _) => (-Self::degrees_to_quarter_turns(n), 0),
+            (Movement::Forward(n), _) => (0, n),
+            (absolute, mode) => {
+                let (direction, n) = absolute.to_dir_val().expect("Absolute movement");
+                let delta = direction.to_coord_delta::<isize>() * n;
+                if mode == TurtleMode::Waypoint {
+                    self.waypoint += delta;
+                } else {
+                    self.coord += delta;
+                }
+                return self;
             }
+        };
+
+        self.turn(quarter_turns);
+        if self.mode == TurtleMode::Waypoint {
+            self.coord += self.waypoint * n;
+        } else {
+            self.facing = self.facing.canonical();
+            self.coord += self.facing.to_coord_delta::<isize>() * n;
         }
         self
     }
@@ -65,6 +200,50 @@ impl Add<Movement> for Turtle {
 
 impl AddAssign<Movement> for Turtle {
     fn add_assign(&mut self, other: Movement) {
-        *self = self.clone() + other;
+        *self = *self + other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(turtle: Turtle, moves: &str) -> Turtle {
+        moves
+            .split_whitespace()
+            .map(|movement| movement.parse::<Movement>().unwrap())
+            .fold(turtle, |turtle, movement| turtle + movement)
+    }
+
+    #[test]
+    fn modes() {
+        let start = Turtle::from(Coord::at(0, 0), Direction::East);
+        let moves = "F10 N3 F7 R90 F11";
+
+        let turtle = apply(start.with_mode(TurtleMode::Degrees), moves);
+        assert_eq!(turtle.coord, Coord::at(17, 8));
+        assert_eq!(turtle.facing, Direction::South);
+
+        let turtle = apply(start, moves);
+        assert_eq!(turtle.coord, Coord::at(17, 98));
+
+        let turtle = apply(
+            start.with_mode(TurtleMode::QuarterTurns),
+            "F2 L1 F3 R2 F1 L3",
+        );
+        assert_eq!(turtle.coord, Coord::at(2, -2));
+        assert_eq!(turtle.facing, Direction::West);
+    }
+
+    #[test]
+    fn waypoint_rotations() {
+        let start = Turtle::new().with_waypoint(Coord::at(3, -1));
+
+        assert_eq!((start + Movement::Right(90)).waypoint(), Coord::at(1, 3));
+        assert_eq!((start + Movement::Left(270)).waypoint(), Coord::at(1, 3));
+        assert_eq!((start + Movement::Left(180)).waypoint(), Coord::at(-3, 1));
+        assert_eq!((start + Movement::Left(90)).waypoint(), Coord::at(-1, -3));
+        assert_eq!((start + Movement::West(2)).waypoint(), Coord::at(1, -1));
+        assert_eq!((start + Movement::Forward(2)).coord, Coord::at(6, -2));
     }
 }
@@ -0,0 +1,493 @@
+//! Define a [Walker], a [Turtle] remembering where it went and that can be bound to a [Grid],
+//! and the [EdgeRule]s deciding what happens when it walks off the grid.
+
+use crate::{Coord, Direction, Grid, Movement, Turtle};
+use anyhow::{ensure, Result};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::AddAssign;
+
+/// What happens to a [Walker] stepping off its [Grid], see [Walker::with_edge_rule].
+/// The void cells (see [Walker::with_void]) are considered off the grid.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum EdgeRule {
+    /// The walker stops in front of the edge.
+    #[default]
+    Stop,
+    /// Leaving a row by its east or west end brings the walker back on the other end of the row.
+    /// The walker stops on the north and south edges.
+    WrapRow,
+    /// Leaving a column by its north or south end brings the walker back on the other end of the
+    /// column. The walker stops on the east and west edges.
+    WrapColumn,
+    /// Wrap around both the rows and the columns.
+    Wrap,
+    /// Leaving the coordinate in the direction of the key teleports the walker on the coordinate
+    /// of the value, now facing its direction. The walker stops on the edges missing from the
+    /// table.
+    Portals(HashMap<(Coord<isize>, Direction), (Coord<isize>, Direction)>),
+}
+
+/// A vector in the 3D space the cube net is folded into.
+type Vec3 = [isize; 3];
+
+/// The orientation of a face of a folded cube: its outward normal and the 3D vectors going east
+/// and south on the net.
+#[derive(Debug, Clone, Copy)]
+struct Face {
+    tile: Coord<isize>,
+    normal: Vec3,
+    east: Vec3,
+    south: Vec3,
+}
+
+impl Face {
+    /// The 3D vector pointing toward `direction` on this face.
+    fn vector(&self, direction: Direction) -> Vec3 {
+        match direction.canonical() {
+            Direction::East => self.east,
+            Direction::West => neg(self.east),
+            Direction::South => self.south,
+            _ => neg(self.south),
+        }
+    }
+
+    /// The face we end up on when folding the net over the edge of this face in `direction`.
+    fn fold(&self, direction: Direction) -> Self {
+        let tile = self.tile + direction.to_coord_delta();
+        let normal = self.vector(direction);
+        match direction.canonical() {
+            Direction::East => Self {
+                tile,
+                normal,
+                east: neg(self.normal),
+                ..*self
+            },
+            Direction::West => Self {
+                tile,
+                normal,
+                east: self.normal,
+                ..*self
+            },
+            Direction::South => Self {
+                tile,
+                normal,
+                south: neg(self.normal),
+                ..*self
+            },
+            _ => Self {
+                tile,
+                normal,
+                south: self.normal,
+                ..*self
+            },
+        }
+    }
+}
+
+fn neg([x, y, z]: Vec3) -> Vec3 {
+    [-x, -y, -z]
+}
+
+fn dot(a: Vec3, b: Vec3) -> isize {
+    (0..3).map(|axis| a[axis] * b[axis]).sum()
+}
+
+impl EdgeRule {
+    /// Fold the cells of the grid that aren't void into a cube and return the portals gluing
+    /// the edges of its faces together. The map must be a cube net made of six square faces.
+    /// ```
+    /// use aoc::{Coord, Direction, EdgeRule, Grid};
+    ///
+    /// let net: Vec<Vec<char>> = ["  #", "###", "  ##"]
+    ///     .iter()
+    ///     .map(|line| format!("{line:4}").chars().collect())
+    ///     .collect();
+    /// let EdgeRule::Portals(portals) = EdgeRule::cube_net(&Grid::from(net), |cell| *cell == ' ').unwrap() else {
+    ///     unreachable!()
+    /// };
+    ///
+    /// assert_eq!(portals.len(), 14);
+    /// assert_eq!(portals[&(Coord::at(2, 0), Direction::North)], (Coord::at(0, 1), Direction::South));
+    /// assert_eq!(portals[&(Coord::at(3, 2), Direction::East)], (Coord::at(2, 0), Direction::West));
+    ///
+    /// assert!(EdgeRule::cube_net(&Grid::from(vec![vec!['#'; 6]]), |cell| *cell == ' ').is_err());
+    /// ```
+    pub fn cube_net<T>(grid: &Grid<T>, is_void: impl Fn(&T) -> bool) -> Result<Self> {
+        let cells = grid.iter().filter(|cell| !is_void(cell)).count();
+        let size = (1..)
+            .find(|size| 6 * size * size >= cells)
+            .expect("Infinite iterator");
+        ensure!(
+            6 * size * size == cells,
+            "{cells} cells can't be split into the six square faces of a cube"
+        );
+
+        let on_map = |coord: Coord<isize>| grid.get(coord).is_some_and(|cell| !is_void(cell));
+        let size = size as isize;
+        let (width, height) = (grid.width() as isize, grid.height() as isize);
+        let tiles: HashSet<Coord<isize>> = Coord::at(0, 0)
+            .to(Coord::at((width - 1) / size, (height - 1) / size))?
+            .filter(|tile| on_map(*tile * size))
+            .collect();
+        ensure!(
+            tiles.len() == 6
+                && tiles.iter().all(|tile| {
+                    Coord::at(0, 0)
+                        .to(Coord::at(size - 1, size - 1))
+                        .expect("The size is positive")
+                        .all(|cell| on_map(*tile * size + cell))
+                }),
+            "The map isn't made of six faces of {size}x{size} cells"
+        );
+
+        let first = *tiles.iter().min().expect("There are six tiles");
+        let mut faces = HashMap::from([(
+            first,
+            Face {
+                tile: first,
+                normal: [0, 0, 1],
+                east: [1, 0, 0],
+                south: [0, 1, 0],
+            },
+        )]);
+        let mut queue = VecDeque::from([first]);
+        while let Some(tile) = queue.pop_front() {
+            let face = faces[&tile];
+            for direction in Direction::all4() {
+                let next = face.fold(direction);
+                if tiles.contains(&next.tile) && !faces.contains_key(&next.tile) {
+                    faces.insert(next.tile, next);
+                    queue.push_back(next.tile);
+                }
+            }
+        }
+        let normals: HashSet<_> = faces.values().map(|face| face.normal).collect();
+        ensure!(
+            faces.len() == 6 && normals.len() == 6,
+            "The map doesn't fold into a cube"
+        );
+
+        let mut portals = HashMap::new();
+        for face in faces.values() {
+            for direction in Direction::all4() {
+                if tiles.contains(&(face.tile + direction.to_coord_delta())) {
+                    continue;
+                }
+                let vector = face.vector(direction);
+                let other = faces
+                    .values()
+                    .find(|other| other.normal == vector)
+                    .expect("All six faces are there");
+                let facing = Direction::all4()
+                    .find(|facing| other.vector(*facing) == neg(face.normal))
+                    .expect("The faces are adjacent");
+
+                for i in 0..size {
+                    let (x, y) = match direction {
+                        Direction::East => (size - 1, i),
+                        Direction::West => (0, i),
+                        Direction::South => (i, size - 1),
+                        _ => (i, 0),
+                    };
+                    // The center of the cell on a cube going from -size to size on every axis.
+                    let center: Vec3 = std::array::from_fn(|axis| {
+                        face.normal[axis] * size
+                            + face.east[axis] * (2 * x + 1 - size)
+                            + face.south[axis] * (2 * y + 1 - size)
+                    });
+                    let target: Vec3 =
+                        std::array::from_fn(|axis| center[axis] + vector[axis] - face.normal[axis]);
+                    let coord = Coord::at(
+                        (dot(target, other.east) + size - 1) / 2,
+                        (dot(target, other.south) + size - 1) / 2,
+                    );
+                    portals.insert(
+                        (face.tile * size + Coord::at(x, y), direction),
+                        (other.tile * size + coord, facing),
+                    );
+                }
+            }
+        }
+        Ok(Self::Portals(portals))
+    }
+}
+
+/// A [Turtle] applying the [Movement]s one step at a time (see [Turtle::steps]) while recording
+/// every coordinate it visits.
+///
+/// When bound to a [Grid] the walker stops in front of the walls, and on the borders of the grid
+/// unless an [EdgeRule] says otherwise. The rest of a blocked movement is dropped but the turns
+/// still happen.
+/// ```
+/// use aoc::{Coord, Direction, Grid, Movement, Turtle, Walker};
+///
+/// let grid = Grid::from(vec![
+///     vec!['.', '.', '.', '#'],
+///     vec!['.', '#', '.', '.'],
+///     vec!['.', '.', '.', '.'],
+/// ]);
+/// let turtle = Turtle::from(Coord::at(0, 0), Direction::East);
+/// let mut walker = Walker::on_grid(turtle, &grid, |cell| *cell == '#');
+///
+/// walker += Movement::Forward(10);
+/// walker += Movement::Right(10);
+/// walker += Movement::West(1);
+///
+/// assert_eq!(walker.turtle.coord, Coord::at(1, 2));
+/// assert_eq!(walker.history(), &[
+///     Coord::at(0, 0), Coord::at(1, 0), Coord::at(2, 0), Coord::at(2, 1), Coord::at(2, 2), Coord::at(1, 2),
+/// ]);
+/// ```
+pub struct Walker<'a, T = ()> {
+    pub turtle: Turtle,
+    history: Vec<Coord<isize>>,
+    grid: Option<(&'a Grid<T>, Predicate<'a, T>)>,
+    is_void: Option<Predicate<'a, T>>,
+    edge_rule: EdgeRule,
+}
+
+type Predicate<'a, T> = Box<dyn Fn(&T) -> bool + 'a>;
+
+impl Walker<'static> {
+    /// A walker moving freely on an infinite plane.
+    pub fn new(turtle: Turtle) -> Self {
+        Self {
+            turtle,
+            history: vec![turtle.coord],
+            grid: None,
+            is_void: None,
+            edge_rule: EdgeRule::Stop,
+        }
+    }
+}
+
+impl<'a, T> Walker<'a, T> {
+    /// A walker that can't leave the `grid` nor walk on the cells where `is_wall` returns `true`.
+    pub fn on_grid(turtle: Turtle, grid: &'a Grid<T>, is_wall: impl Fn(&T) -> bool + 'a) -> Self {
+        Self {
+            turtle,
+            history: vec![turtle.coord],
+            grid: Some((grid, Box::new(is_wall))),
+            is_void: None,
+            edge_rule: EdgeRule::Stop,
+        }
+    }
+
+    /// Consider the cells where `is_void` returns `true` as outside of the grid, to walk on
+    /// maps that aren't rectangular.
+    pub fn with_void(self, is_void: impl Fn(&T) -> bool + 'a) -> Self {
+        Self {
+            is_void: Some(Box::new(is_void)),
+            ..self
+        }
+    }
+
+    /// Choose what happens when the walker steps off the grid, see [EdgeRule].
+    /// ```
+    /// use aoc::{Coord, Direction, EdgeRule, Grid, Movement, Turtle, Walker};
+    ///
+    /// let grid = Grid::from(vec![
+    ///     vec![' ', '.', '.', '.'],
+    ///     vec![' ', '.', '#', '.'],
+    ///     vec!['.', '.', '.', ' '],
+    /// ]);
+    /// let turtle = Turtle::from(Coord::at(3, 0), Direction::East);
+    /// let mut walker = Walker::on_grid(turtle, &grid, |cell| *cell == '#')
+    ///     .with_void(|cell| *cell == ' ')
+    ///     .with_edge_rule(EdgeRule::Wrap);
+    ///
+    /// walker += Movement::Forward(2);
+    /// assert_eq!(walker.turtle.coord, Coord::at(2, 0));
+    /// walker += Movement::South(2);
+    /// assert_eq!(walker.turtle.coord, Coord::at(2, 0));
+    /// walker += Movement::North(2);
+    /// assert_eq!(walker.turtle.coord, Coord::at(2, 2));
+    /// ```
+    pub fn with_edge_rule(self, edge_rule: EdgeRule) -> Self {
+        Self { edge_rule, ..self }
+    }
+
+    /// Every coordinate visited by the walker, in order, starting with its starting position.
+    pub fn history(&self) -> &[Coord<isize>] {
+        &self.history
+    }
+
+    /// Return `true` if the coordinate is outside of the grid or on a void cell.
+    pub fn is_off_grid(&self, coord: Coord<isize>) -> bool {
+        match &self.grid {
+            None => false,
+            Some((grid, _)) => grid
+                .get(coord)
+                .is_none_or(|cell| self.is_void.as_ref().is_some_and(|is_void| is_void(cell))),
+        }
+    }
+
+    /// Return `true` if the walker can't go on this coordinate.
+    pub fn is_blocked(&self, coord: Coord<isize>) -> bool {
+        match &self.grid {
+            None => false,
+            Some((grid, is_wall)) => self.is_off_grid(coord) || grid.get(coord).is_none_or(is_wall),
+        }
+    }
+
+    /// Where the walker lands when `moved` from `from` ended off the grid, according to the
+    /// [EdgeRule].
+    fn cross_edge(&self, from: Coord<isize>, moved: Turtle) -> Option<Turtle> {
+        let direction = Direction::from_coord_delta(moved.coord - from)?;
+        let wrap = match self.edge_rule {
+            EdgeRule::Stop => false,
+            EdgeRule::WrapRow => direction.is_horizontal(),
+            EdgeRule::WrapColumn => direction.is_vertical(),
+            EdgeRule::Wrap => true,
+            EdgeRule::Portals(ref portals) => {
+                let (coord, facing) = portals.get(&(from, direction))?;
+                return Some(Turtle {
+                    coord: *coord,
+                    facing: *facing,
+                    ..moved
+                });
+            }
+        };
+        if !wrap {
+            return None;
+        }
+        let delta = direction.to_coord_delta();
+        let mut coord = from;
+        while !self.is_off_grid(coord - delta) {
+            coord -= delta;
+        }
+        Some(Turtle { coord, ..moved })
+    }
+
+    /// Apply a movement one step at a time. Returns `false` if the walker was blocked before the
+    /// end of the movement.
+    pub fn walk(&mut self, movement: Movement) -> bool {
+        for step in self.turtle.steps(movement) {
+            let moved = self.turtle + step;
+            let next = if self.is_off_grid(moved.coord) {
+                self.cross_edge(self.turtle.coord, moved).unwrap_or(moved)
+            } else {
+                moved
+            };
+            if next.coord == self.turtle.coord {
+                self.turtle = next;
+            } else if self.is_blocked(next.coord) {
+                self.turtle = Turtle {
+                    coord: self.turtle.coord,
+                    ..moved
+                };
+                return false;
+            } else {
+                self.turtle = next;
+                self.history.push(next.coord);
+            }
+        }
+        true
+    }
+}
+
+impl<T> AddAssign<Movement> for Walker<'_, T> {
+    fn add_assign(&mut self, movement: Movement) {
+        self.walk(movement);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Direction, MovementParser, TurtleMode};
+
+    #[test]
+    fn free_walker_matches_path() {
+        let moves = [
+            Movement::Forward(3),
+            Movement::Right(2),
+            Movement::Left(-4),
+            Movement::South(-2),
+        ];
+        let turtle = Turtle::from(Coord::at(1, 1), Direction::West);
+        let mut walker = Walker::new(turtle);
+        for movement in moves {
+            assert!(walker.walk(movement));
+        }
+
+        let path = Movement::trace(turtle, moves);
+        assert_eq!(walker.history(), path.points().collect::<Vec<_>>());
+        assert_eq!(walker.turtle, path.end());
+    }
+
+    #[test]
+    fn blocked_turns_still_happen() {
+        let grid = Grid::from(vec![vec!['.', '.'], vec!['#', '.']]);
+        let turtle = Turtle::from(Coord::at(0, 0), Direction::North).with_mode(TurtleMode::Degrees);
+        let mut walker = Walker::on_grid(turtle, &grid, |cell| *cell == '#');
+
+        assert!(!walker.walk(Movement::Forward(1)));
+        assert!(walker.walk(Movement::Left(180)));
+        assert!(!walker.walk(Movement::Forward(1)));
+        assert_eq!(walker.turtle.facing, Direction::South);
+        assert!(walker.walk(Movement::East(1)));
+        assert!(walker.walk(Movement::Forward(1)));
+        assert!(!walker.walk(Movement::Forward(1)));
+
+        assert_eq!(
+            walker.history(),
+            &[Coord::at(0, 0), Coord::at(1, 0), Coord::at(1, 1)]
+        );
+    }
+
+    fn monkey_map(edge_rule: impl Fn(&Grid<char>) -> EdgeRule) -> isize {
+        let map = [
+            "        ...#",
+            "        .#..",
+            "        #...",
+            "        ....",
+            "...#.......#",
+            "........#...",
+            "..#....#....",
+            "..........#.",
+            "        ...#....",
+            "        .....#..",
+            "        .#......",
+            "        ......#.",
+        ];
+        let grid = Grid::from(
+            map.iter()
+                .map(|line| format!("{line:16}").chars().collect())
+                .collect(),
+        );
+        let start =
+            Turtle::from(Coord::at(8, 0), Direction::East).with_mode(TurtleMode::QuarterTurns);
+        let mut walker = Walker::on_grid(start, &grid, |cell| *cell == '#')
+            .with_void(|cell| *cell == ' ')
+            .with_edge_rule(edge_rule(&grid));
+
+        let parser = MovementParser::default();
+        for movement in [
+            "F10", "R1", "F5", "L1", "F5", "R1", "F10", "L1", "F4", "R1", "F5", "L1", "F5",
+        ] {
+            walker += parser.parse(movement).unwrap();
+        }
+
+        let Turtle { coord, facing, .. } = walker.turtle;
+        let facing = [
+            Direction::East,
+            Direction::South,
+            Direction::West,
+            Direction::North,
+        ]
+        .iter()
+        .position(|direction| *direction == facing)
+        .unwrap() as isize;
+        1000 * (coord.y + 1) + 4 * (coord.x + 1) + facing
+    }
+
+    #[test]
+    fn wrapping_edges() {
+        assert_eq!(monkey_map(|_| EdgeRule::Wrap), 6032);
+        assert_eq!(
+            monkey_map(|grid| EdgeRule::cube_net(grid, |cell| *cell == ' ').unwrap()),
+            5031
+        );
+    }
+}